@@ -0,0 +1,49 @@
+use std::{
+    io,
+    process::{Command, Stdio},
+};
+
+/// Default for the `url_template` config key.
+pub const DEFAULT_URL_TEMPLATE: &str = "https://www.binance.com/en/trade/{base}_{quote}";
+
+/// Fill `{base}`, `{quote}` and `{symbol}` in `template`.
+pub fn url(template: &str, base: &str, quote: &str) -> String {
+    template
+        .replace("{base}", base)
+        .replace("{quote}", quote)
+        .replace("{symbol}", &format!("{base}{quote}"))
+}
+
+/// Whether there is a browser to open; on Linux and BSD that needs a
+/// graphical session.
+pub fn available() -> bool {
+    cfg!(any(target_os = "macos", windows))
+        || std::env::var_os("DISPLAY").is_some()
+        || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+/// Open `url` in the default browser without waiting for it.
+pub fn open(url: &str) -> io::Result<()> {
+    if !available() {
+        // xdg-open falls back to text browsers that would take over the TUI
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "no graphical session to open a browser in",
+        ));
+    }
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        // not `cmd /C start`, which would split the URL at any `&`
+        Command::new("explorer")
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(drop)
+}
@@ -0,0 +1,100 @@
+use std::{
+    borrow::Borrow,
+    collections::HashMap,
+    hash::Hash,
+    time::{Duration, Instant},
+};
+
+/// Values fetched per key, such as a symbol's week-ago close, kept for
+/// `ttl` before they are due to be fetched again. Holds at most `capacity`
+/// entries, dropping the oldest to make room.
+#[derive(Clone, Debug)]
+pub struct TtlCache<K, V> {
+    ttl: Duration,
+    capacity: usize,
+    /// Value, when it was stored and the insertion count at the time, the
+    /// lowest count being the oldest entry.
+    entries: HashMap<K, (V, Instant, u64)>,
+    inserted: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> TtlCache<K, V> {
+    pub fn new(ttl: Duration, capacity: usize) -> Self {
+        Self {
+            ttl,
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            inserted: 0,
+        }
+    }
+
+    /// The value of `key` if it was stored within the TTL.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries
+            .get(key)
+            .filter(|(_, stored, _)| stored.elapsed() < self.ttl)
+            .map(|(value, _, _)| value)
+    }
+
+    /// The value of `key` however old, to show until a fresh one arrives.
+    pub fn peek<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key).map(|(value, _, _)| value)
+    }
+
+    /// Whether `key` needs fetching: it is missing or older than the TTL.
+    pub fn is_due<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.get(key).is_none()
+    }
+
+    /// Store `value` for `key`, evicting the oldest entry when full.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            // a linear scan, cheap next to the request that filled the entry
+            let oldest = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (_, _, inserted))| *inserted)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.inserted += 1;
+        self.entries
+            .insert(key, (value, Instant::now(), self.inserted));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expired_entries_are_due_and_the_oldest_is_evicted() {
+        let mut cache = TtlCache::new(Duration::from_secs(60), 2);
+        cache.insert("BTCUSDT".to_owned(), 1.0);
+        cache.insert("ETHUSDT".to_owned(), 2.0);
+        assert_eq!(cache.get("BTCUSDT"), Some(&1.0));
+        assert!(!cache.is_due("ETHUSDT"));
+        cache.insert("SOLUSDT".to_owned(), 3.0);
+        assert_eq!(cache.peek("BTCUSDT"), None);
+        assert_eq!(cache.get("SOLUSDT"), Some(&3.0));
+
+        let mut expired = TtlCache::new(Duration::ZERO, 2);
+        expired.insert("BTCUSDT".to_owned(), 1.0);
+        assert!(expired.is_due("BTCUSDT"));
+        assert_eq!(expired.peek("BTCUSDT"), Some(&1.0));
+    }
+}
@@ -0,0 +1,99 @@
+use std::{net::SocketAddr, path::PathBuf};
+
+use color_eyre::eyre::{bail, Result};
+
+const USAGE: &str = "Usage: coinmarket [OPTIONS]
+       coinmarket favorites export <FILE>
+       coinmarket favorites import <FILE>
+       coinmarket snapshot <FILE>
+
+Commands:
+  favorites export  Write favorites to FILE (JSON for .json, else one per line)
+  favorites import  Merge favorites from FILE into the config
+  snapshot          Save the current prices to FILE for --compare
+
+Options:
+      --mono              Render without colors (also enabled by NO_COLOR)
+      --compare <A> <B>   Show the price change between two snapshots
+      --check-config      Report problems in the config file and exit
+      --no-altscreen      Draw on the main screen and print the table on exit
+      --serve <ADDR>      Also serve the shown rows as JSON, e.g. 127.0.0.1:8080
+  -h, --help              Print this help";
+
+/// A one-shot command run instead of the TUI.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    ExportFavorites(PathBuf),
+    ImportFavorites(PathBuf),
+    Snapshot(PathBuf),
+    CheckConfig,
+}
+
+/// Command line options.
+#[derive(Clone, Debug, Default)]
+pub struct Args {
+    /// Render the UI without colors, relying on text modifiers instead.
+    pub mono: bool,
+    pub command: Option<Command>,
+    /// Draw on the main screen instead of the alternate one, printing the
+    /// last table on exit so it stays in the scrollback.
+    pub no_altscreen: bool,
+    /// Address to serve the shown rows on as JSON while the TUI runs.
+    pub serve: Option<SocketAddr>,
+    /// Two snapshots to compare instead of showing live prices.
+    pub compare: Option<(PathBuf, PathBuf)>,
+    /// Log render, fetch and update timings periodically. Not in the usage
+    /// text since it is only meant for performance reports.
+    pub debug_metrics: bool,
+}
+
+impl Args {
+    pub fn parse() -> Result<Self> {
+        let mut args = Self::from_iter(std::env::args().skip(1))?;
+        // https://no-color.org: any non-empty value disables color
+        if std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty()) {
+            args.mono = true;
+        }
+        Ok(args)
+    }
+
+    fn from_iter(iter: impl IntoIterator<Item = String>) -> Result<Self> {
+        let mut args = Self::default();
+        let mut iter = iter.into_iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--mono" => args.mono = true,
+                "--debug-metrics" => args.debug_metrics = true,
+                "--no-altscreen" => args.no_altscreen = true,
+                "--check-config" => args.command = Some(Command::CheckConfig),
+                "--serve" => match iter.next().map(|addr| addr.parse()) {
+                    Some(Ok(addr)) => args.serve = Some(addr),
+                    Some(Err(e)) => bail!("invalid address for --serve: {e}\n\n{USAGE}"),
+                    None => bail!("expected '--serve <ADDR>'\n\n{USAGE}"),
+                },
+                "favorites" => {
+                    let (action, path) = (iter.next(), iter.next().map(PathBuf::from));
+                    args.command = Some(match (action.as_deref(), path) {
+                        (Some("export"), Some(path)) => Command::ExportFavorites(path),
+                        (Some("import"), Some(path)) => Command::ImportFavorites(path),
+                        _ => bail!("expected 'favorites export|import <FILE>'\n\n{USAGE}"),
+                    });
+                }
+                "snapshot" => match iter.next() {
+                    Some(path) => args.command = Some(Command::Snapshot(PathBuf::from(path))),
+                    None => bail!("expected 'snapshot <FILE>'\n\n{USAGE}"),
+                },
+                "--compare" => match (iter.next(), iter.next()) {
+                    (Some(a), Some(b)) => args.compare = Some((a.into(), b.into())),
+                    _ => bail!("expected '--compare <A> <B>'\n\n{USAGE}"),
+                },
+                "-h" | "--help" => {
+                    println!("{USAGE}");
+                    std::process::exit(0);
+                }
+                other => bail!("unknown argument '{other}'\n\n{USAGE}"),
+            }
+        }
+        Ok(args)
+    }
+}
@@ -0,0 +1,63 @@
+use std::io::{IsTerminal, Write};
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+/// Table format for copying rows with `Y`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CopyFormat {
+    /// A GitHub-flavored markdown table, for chat and issues.
+    #[default]
+    Markdown,
+    /// Tab separated values, for spreadsheets.
+    Tsv,
+}
+
+impl CopyFormat {
+    /// File extension used when the clipboard is unavailable.
+    pub fn extension(self) -> &'static str {
+        match self {
+            CopyFormat::Markdown => "md",
+            CopyFormat::Tsv => "tsv",
+        }
+    }
+
+    /// Lay out `headers` and `rows` as a table in this format.
+    pub fn table(self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        let line = |cells: Vec<&str>| match self {
+            CopyFormat::Markdown => format!("| {} |\n", cells.join(" | ")),
+            CopyFormat::Tsv => format!("{}\n", cells.join("\t")),
+        };
+        let mut out = line(headers.to_vec());
+        if self == CopyFormat::Markdown {
+            out.push_str(&line(headers.iter().map(|_| "---").collect()));
+        }
+        for row in rows {
+            out.push_str(&line(row.iter().map(String::as_str).collect()));
+        }
+        out
+    }
+}
+
+/// Whether there is a terminal to send the clipboard escape to.
+pub fn available() -> bool {
+    std::io::stderr().is_terminal()
+}
+
+/// Copy `text` to the system clipboard using the OSC 52 terminal escape.
+///
+/// This works over SSH and without any clipboard daemon, as long as the
+/// terminal emulator supports OSC 52; unsupported terminals ignore it.
+pub fn copy(text: &str) -> std::io::Result<()> {
+    let mut stderr = std::io::stderr();
+    if !available() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "no terminal to copy through",
+        ));
+    }
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    write!(stderr, "\x1b]52;c;{encoded}\x07")?;
+    stderr.flush()
+}
@@ -0,0 +1,65 @@
+use std::{cmp::Ordering, collections::BTreeMap};
+
+use crate::MarketRow;
+
+/// One symbol's price in two snapshots. A price is `None` when the symbol
+/// is missing from that snapshot.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Comparison {
+    pub symbol: String,
+    pub price_a: Option<f64>,
+    pub price_b: Option<f64>,
+}
+
+impl Comparison {
+    /// Change from `a` to `b` in percent, when both prices are known.
+    pub fn delta_pct(&self) -> Option<f64> {
+        match (self.price_a, self.price_b) {
+            (Some(a), Some(b)) if a != 0.0 => Some((b - a) / a * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Join two snapshots by symbol, ordered by symbol.
+pub fn compare(a: &[MarketRow], b: &[MarketRow]) -> Vec<Comparison> {
+    let mut joined = BTreeMap::<&str, Comparison>::new();
+    let rows = a
+        .iter()
+        .map(|row| (row, true))
+        .chain(b.iter().map(|row| (row, false)));
+    for (row, in_a) in rows {
+        let entry = joined.entry(&row.symbol).or_insert_with(|| Comparison {
+            symbol: row.symbol.clone(),
+            ..Comparison::default()
+        });
+        if in_a {
+            entry.price_a = Some(row.price);
+        } else {
+            entry.price_b = Some(row.price);
+        }
+    }
+    joined.into_values().collect()
+}
+
+/// Sort by delta when `by_delta`, else by symbol. Symbols without a delta
+/// always sort last.
+pub fn sort_comparisons(rows: &mut [Comparison], by_delta: bool, descending: bool) {
+    let directed = |ordering: Ordering| {
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    if by_delta {
+        rows.sort_by(|a, b| match (a.delta_pct(), b.delta_pct()) {
+            (Some(a), Some(b)) => directed(a.total_cmp(&b)),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => a.symbol.cmp(&b.symbol),
+        });
+    } else {
+        rows.sort_by(|a, b| directed(a.symbol.cmp(&b.symbol)));
+    }
+}
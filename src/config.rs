@@ -0,0 +1,778 @@
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use coinmarket::{sort::SortMode, Credentials};
+use color_eyre::eyre::{eyre, Result, WrapErr};
+use crossterm::event::KeyCode;
+
+use crate::clipboard::CopyFormat;
+use serde::{Deserialize, Serialize};
+
+const CONFIG_FILE: &str = "config.json";
+
+/// Every key of [`Config`], for reporting unknown ones.
+const KEYS: &[&str] = &[
+    "locale",
+    "refresh_interval",
+    "refresh_jitter",
+    "row_height",
+    "row_padding",
+    "quote_asset",
+    "precision",
+    "quote_precision",
+    "theme",
+    "navigation",
+    "wrap_navigation",
+    "striped",
+    "selection_style",
+    "flash_duration",
+    "change_flash_duration",
+    "flash_up_color",
+    "flash_down_color",
+    "move_alert",
+    "max_staleness",
+    "inactivity_timeout",
+    "ticker_interval",
+    "refresh_on_focus",
+    "hold_behind_popups",
+    "url_template",
+    "favorites",
+    "last_version",
+    "timezone",
+    "render_buffer",
+    "history_len",
+    "persist_history",
+    "startup_view",
+    "sort",
+    "sort_descending",
+    "quick_filters",
+    "credentials_file",
+    "copy_format",
+    "exact_search",
+    "stablecoins",
+    "depeg_threshold",
+    "aliases",
+    "quote_tabs",
+    "notes",
+    "alerts",
+];
+
+/// Something [`Config::check`] found wrong, at the line of the offending
+/// key when it could be found.
+#[derive(Debug, PartialEq)]
+pub struct Problem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// User settings read from `$XDG_CONFIG_HOME/coinmarket/config.json`
+/// (or `~/.config/coinmarket/config.json`). Every field is optional so a
+/// partial file only overrides what it mentions.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Locale used for number formatting, e.g. `en-US` or `de_DE`.
+    /// Falls back to the system locale when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Auto-refresh interval in seconds. Auto-refresh starts enabled when
+    /// this is set; it can always be toggled with `a`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_interval: Option<u32>,
+    /// Up to this many seconds are added at random to each auto-refresh
+    /// interval, so instances started together drift apart (default 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_jitter: Option<u32>,
+    /// Height of each table row in lines (at least 1).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_height: Option<u16>,
+    /// Blank lines above the content of each row.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_padding: Option<u16>,
+    /// Quote asset whose pairs are listed (default `USDT`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quote_asset: Option<String>,
+    /// Fixed number of decimals for prices; all significant digits when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub precision: Option<usize>,
+    /// Price decimals per quote asset used when `precision` is unset, on top
+    /// of built-in defaults such as 8 for BTC and 4 for USDT.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub quote_precision: BTreeMap<String, usize>,
+    /// Color theme: `blue`, `emerald`, `indigo` or `red`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Rows shown at startup: `all` (default), `favorites` or `movers`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub startup_view: Option<ViewMode>,
+    /// Column sorted by at startup.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<SortColumn>,
+    /// Direction of `sort`; names default to ascending, numbers to
+    /// descending.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_descending: Option<bool>,
+    /// Keys that move the selection: `vim`, `arrows` or `both` (default).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub navigation: Option<Navigation>,
+    /// Whether moving past the last row selects the first and the other
+    /// way around (default true); otherwise the selection stops there.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap_navigation: Option<bool>,
+    /// Alternate the background of every other row (default true).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub striped: Option<bool>,
+    /// How the selected row is highlighted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_style: Option<SelectionStyle>,
+    /// Seconds a footer message such as "Copied 2 rows" stays up (default 5).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flash_duration: Option<u32>,
+    /// Seconds a price that changed in a refresh stays highlighted
+    /// (default 2, 0 turns the highlight off).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_flash_duration: Option<u32>,
+    /// Background of a price that went up, e.g. `green` or `#1e6b34`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flash_up_color: Option<String>,
+    /// Background of a price that went down.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub flash_down_color: Option<String>,
+    /// Percent a favorite may move between two refreshes before the
+    /// terminal bell rings; no bell when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub move_alert: Option<f64>,
+    /// Seconds without a successful refresh after which the table is
+    /// greyed out under a stale data banner; off when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_staleness: Option<u64>,
+    /// Quit after this many seconds without a keypress; refreshes do not
+    /// count. Off when unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inactivity_timeout: Option<u64>,
+    /// Seconds each row stays selected in the ticker mode toggled with `A`
+    /// (default 3).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ticker_interval: Option<u32>,
+    /// Rows built above and below the visible ones when drawing the table,
+    /// the rest being skipped (default 10).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub render_buffer: Option<usize>,
+    /// Prices kept per symbol for the chart pane (default 240).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub history_len: Option<usize>,
+    /// Save the favorites' chart history on quit and load it on launch
+    /// (default false).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub persist_history: Option<bool>,
+    /// Refresh when the terminal regains focus (default true).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_on_focus: Option<bool>,
+    /// Hold refreshed rows back while a popup or the search box is open,
+    /// showing them once it closes (default true).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_behind_popups: Option<bool>,
+    /// Page opened with `o`; `{base}`, `{quote}` and `{symbol}` are replaced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url_template: Option<String>,
+    /// Favorited symbols, toggled with `f` in the table.
+    #[serde(skip_serializing_if = "BTreeSet::is_empty")]
+    pub favorites: BTreeSet<String>,
+    /// Time zone of shown timestamps and export names: `local` (default),
+    /// `UTC` or an offset such as `+05:30`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// Version of the last run, to show what's new after an upgrade.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_version: Option<String>,
+    /// JSON file holding `api_key` and `secret_key`. The keys themselves are
+    /// never stored in this config, so saving it cannot leak them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub credentials_file: Option<PathBuf>,
+    /// Table format used by `Y`: `markdown` (default) or `tsv`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub copy_format: Option<CopyFormat>,
+    /// Fetch only the exact pair for a search such as `BTC`, instead of
+    /// every price, falling back to a prefix search when it is not listed.
+    /// Saves bandwidth, but each search and refresh is a new request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exact_search: Option<bool>,
+    /// Quote assets compared by the stablecoin view (`g`), the first being
+    /// the reference; USDT, USDC, FDUSD, TUSD, BUSD and DAI when empty.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub stablecoins: Vec<String>,
+    /// Spread in percent above which the stablecoin view highlights a price
+    /// (default 0.5).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depeg_threshold: Option<f64>,
+    /// Quote assets shown as tabs above the table, switched with the left
+    /// and right keys or a click; `*` is a tab of every quote asset.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quote_tabs: Vec<String>,
+    /// Named symbol groups shown with the keys 1 to 9, in order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub quick_filters: Vec<QuickFilter>,
+    /// Friendly names searched as a base asset, e.g. `{"bitcoin": "BTC"}`.
+    /// Names match case-insensitively.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub aliases: BTreeMap<String, String>,
+    /// Personal notes per symbol, edited with `n` and marked in the table.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub notes: BTreeMap<String, String>,
+    /// Price targets managed on the alerts screen (`B`).
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<PriceAlert>,
+    /// Set when the file exists but could not be read or parsed, so these
+    /// defaults are never saved over it.
+    #[serde(skip)]
+    pub load_failed: bool,
+}
+
+/// Which keys move the selection.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Navigation {
+    /// `h`/`j`/`k`/`l` only.
+    Vim,
+    /// Arrow keys only.
+    Arrows,
+    /// Both vim keys and arrows.
+    #[default]
+    Both,
+}
+
+impl Navigation {
+    /// Map the vim keys of this scheme onto arrows, so key handling only
+    /// needs to match arrows. Arrows the scheme does not use become
+    /// [`KeyCode::Null`]; everything else passes through.
+    pub fn translate(self, code: KeyCode) -> KeyCode {
+        let vim = match code {
+            KeyCode::Char('h') => Some(KeyCode::Left),
+            KeyCode::Char('j') => Some(KeyCode::Down),
+            KeyCode::Char('k') => Some(KeyCode::Up),
+            KeyCode::Char('l') => Some(KeyCode::Right),
+            _ => None,
+        };
+        let arrow = matches!(
+            code,
+            KeyCode::Left | KeyCode::Down | KeyCode::Up | KeyCode::Right
+        );
+        match (self, vim) {
+            (Navigation::Vim | Navigation::Both, Some(arrow)) => arrow,
+            (Navigation::Vim, None) if arrow => KeyCode::Null,
+            _ => code,
+        }
+    }
+}
+
+/// Side of the target a price alert fires on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertDirection {
+    Above,
+    Below,
+}
+
+impl AlertDirection {
+    pub fn label(self) -> &'static str {
+        match self {
+            AlertDirection::Above => "above",
+            AlertDirection::Below => "below",
+        }
+    }
+}
+
+/// A price target, e.g. `{"symbol": "BTCUSDT", "target": 70000, "direction":
+/// "above"}`. It rings once and stays listed as triggered until deleted or
+/// edited.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PriceAlert {
+    pub symbol: String,
+    pub target: f64,
+    pub direction: AlertDirection,
+    #[serde(default)]
+    pub triggered: bool,
+}
+
+impl PriceAlert {
+    /// Whether `price` is on the alerting side of the target.
+    pub fn reached(&self, price: f64) -> bool {
+        match self.direction {
+            AlertDirection::Above => price >= self.target,
+            AlertDirection::Below => price <= self.target,
+        }
+    }
+
+    /// Parse a target typed on the alerts screen: `>70000` or `<60000`,
+    /// or a plain price whose side follows from the current `price`.
+    pub fn parse_target(input: &str, price: Option<f64>) -> Result<(f64, AlertDirection), String> {
+        let input = input.trim();
+        let (direction, number) = match input.chars().next() {
+            Some('>') => (Some(AlertDirection::Above), &input[1..]),
+            Some('<') => (Some(AlertDirection::Below), &input[1..]),
+            _ => (None, input),
+        };
+        let target = number
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|target| target.is_finite() && *target > 0.0)
+            .ok_or_else(|| format!("{input:?} is not a price like 70000, >70000 or <60000"))?;
+        let direction = direction.unwrap_or(match price {
+            Some(price) if target < price => AlertDirection::Below,
+            _ => AlertDirection::Above,
+        });
+        Ok((target, direction))
+    }
+}
+
+/// A named group of coins, e.g. `{"name": "L1s", "symbols": ["BTC", "ETH"]}`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QuickFilter {
+    pub name: String,
+    /// Base assets such as `BTC`, or whole symbols such as `BTCUSDT`.
+    pub symbols: Vec<String>,
+}
+
+impl QuickFilter {
+    /// Whether `symbol`, quoted in `quote`, is in the group.
+    pub fn contains(&self, symbol: &str, quote: &str) -> bool {
+        let base = symbol.strip_suffix(quote).unwrap_or(symbol);
+        self.symbols
+            .iter()
+            .any(|entry| entry.eq_ignore_ascii_case(symbol) || entry.eq_ignore_ascii_case(base))
+    }
+}
+
+/// Which rows the table shows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ViewMode {
+    /// Every pair of the search.
+    #[default]
+    All,
+    /// Favorited pairs only.
+    Favorites,
+    /// The pairs with the largest 24h change either way.
+    Movers,
+}
+
+impl ViewMode {
+    /// The next mode in the cycle All → Favorites → Movers → All.
+    pub fn next(self) -> Self {
+        match self {
+            ViewMode::All => ViewMode::Favorites,
+            ViewMode::Favorites => ViewMode::Movers,
+            ViewMode::Movers => ViewMode::All,
+        }
+    }
+}
+
+/// Column sorted by at startup, see [`Config::sort`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortColumn {
+    Symbol,
+    Price,
+    Change,
+    Volume,
+}
+
+impl From<SortColumn> for SortMode {
+    fn from(column: SortColumn) -> Self {
+        match column {
+            SortColumn::Symbol => SortMode::Symbol,
+            SortColumn::Price => SortMode::Price,
+            SortColumn::Change => SortMode::Change,
+            SortColumn::Volume => SortMode::Volume,
+        }
+    }
+}
+
+/// How the selected table row is highlighted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SelectionStyle {
+    /// Swap the row's foreground and background.
+    #[default]
+    Reversed,
+    /// Fill the row with the theme's accent color.
+    Background,
+}
+
+/// Contents of [`Config::credentials_file`].
+#[derive(Deserialize)]
+struct CredentialsFile {
+    api_key: String,
+    secret_key: String,
+}
+
+impl Config {
+    /// Location of the config file, honouring `COINMARKET_CONFIG` first.
+    pub fn path() -> Option<PathBuf> {
+        if let Some(path) = std::env::var_os("COINMARKET_CONFIG") {
+            return Some(PathBuf::from(path));
+        }
+        let base = match std::env::var_os("XDG_CONFIG_HOME") {
+            Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+        };
+        Some(base.join("coinmarket").join(CONFIG_FILE))
+    }
+
+    /// Load the config file, falling back to defaults when it is missing or
+    /// cannot be parsed. Defaults standing in for a broken file are marked
+    /// [`Config::load_failed`].
+    pub fn load() -> Self {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Self::default(),
+        }
+    }
+
+    fn load_from(path: &Path) -> Self {
+        let failed = || Self {
+            load_failed: true,
+            ..Self::default()
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Self::default(),
+            Err(e) => {
+                log::warn!("Unable to read config {}: {}", path.display(), e);
+                return failed();
+            }
+        };
+        match serde_json::from_str(&contents) {
+            Ok(config) => config,
+            Err(e) => {
+                log::warn!("Ignoring invalid config {}: {}", path.display(), e);
+                failed()
+            }
+        }
+    }
+
+    /// Every problem in the config file `contents`, where [`Config::load`]
+    /// would warn about the first and fall back to defaults.
+    pub fn check(contents: &str) -> Vec<Problem> {
+        let object = match serde_json::from_str::<serde_json::Value>(contents) {
+            Ok(serde_json::Value::Object(object)) => object,
+            Ok(_) => {
+                return vec![Problem {
+                    line: Some(1),
+                    message: "expected an object of settings".to_owned(),
+                }]
+            }
+            Err(e) => {
+                return vec![Problem {
+                    line: Some(e.line()),
+                    message: e.to_string(),
+                }]
+            }
+        };
+        // the key as written, the first line it is on being the best guess
+        let line_of = |key: &str| {
+            let quoted = format!("\"{key}\"");
+            contents
+                .lines()
+                .position(|line| line.contains(&quoted))
+                .map(|i| i + 1)
+        };
+        let mut problems = Vec::new();
+        let mut problem = |key: &str, message: String| {
+            problems.push(Problem {
+                line: line_of(key),
+                message: format!("{key}: {message}"),
+            });
+        };
+        for (key, value) in &object {
+            if !KEYS.contains(&key.as_str()) {
+                problem(key, "unknown setting".to_owned());
+                continue;
+            }
+            // one key at a time, so one bad value does not hide the others
+            let single =
+                serde_json::Value::Object([(key.clone(), value.clone())].into_iter().collect());
+            let config = match serde_json::from_value::<Config>(single) {
+                Ok(config) => config,
+                Err(e) => {
+                    problem(key, e.to_string());
+                    continue;
+                }
+            };
+            let range = match key.as_str() {
+                "refresh_interval" => config
+                    .refresh_interval
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "flash_duration" => config
+                    .flash_duration
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "row_height" => config
+                    .row_height
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "inactivity_timeout" => config
+                    .inactivity_timeout
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "ticker_interval" => config
+                    .ticker_interval
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "history_len" => config
+                    .history_len
+                    .filter(|x| *x < 2)
+                    .map(|_| "must be at least 2"),
+                "max_staleness" => config
+                    .max_staleness
+                    .filter(|x| *x == 0)
+                    .map(|_| "must be at least 1"),
+                "move_alert" => config
+                    .move_alert
+                    .filter(|x| *x <= 0.0)
+                    .map(|_| "must be above 0"),
+                "depeg_threshold" => config
+                    .depeg_threshold
+                    .filter(|x| *x <= 0.0)
+                    .map(|_| "must be above 0"),
+                "theme" => config
+                    .theme
+                    .filter(|theme| {
+                        !crate::PALETTE_NAMES
+                            .iter()
+                            .any(|name| name.eq_ignore_ascii_case(theme))
+                    })
+                    .map(|_| "must be blue, emerald, indigo or red"),
+                "locale" => config
+                    .locale
+                    .filter(|locale| {
+                        coinmarket::format::NumberFormat::from_locale(locale).is_none()
+                    })
+                    .map(|_| "is not a known locale"),
+                "quick_filters" => {
+                    (config.quick_filters.len() > 9).then_some("only the first 9 have a key")
+                }
+                "flash_up_color" => config
+                    .flash_up_color
+                    .filter(|color| color.parse::<ratatui::style::Color>().is_err())
+                    .map(|_| "must be a color name such as green or a hex code such as #1e6b34"),
+                "flash_down_color" => config
+                    .flash_down_color
+                    .filter(|color| color.parse::<ratatui::style::Color>().is_err())
+                    .map(|_| "must be a color name such as red or a hex code such as #8b1e1e"),
+                "timezone" => config
+                    .timezone
+                    .filter(|name| crate::timezone::TimeZone::parse(name).is_none())
+                    .map(|_| "must be local, UTC or an offset such as +05:30"),
+                _ => None,
+            };
+            if let Some(message) = range {
+                problem(key, message.to_owned());
+            }
+        }
+        if let Some(padding) = object
+            .get("row_padding")
+            .and_then(serde_json::Value::as_u64)
+        {
+            let height = object
+                .get("row_height")
+                .and_then(serde_json::Value::as_u64)
+                .unwrap_or(crate::DEFAULT_ROW_HEIGHT.into());
+            if padding >= height {
+                problem("row_padding", format!("must be below row_height {height}"));
+            }
+        }
+        // keys come back sorted by name
+        problems.sort_by_key(|problem| problem.line);
+        problems
+    }
+
+    /// `--check-config`: print the problems of the config file with their
+    /// lines, exiting with status 1 when there are any.
+    pub fn check_file() -> Result<()> {
+        let path = Self::path().ok_or_else(|| eyre!("no config directory"))?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            // as good as an empty file, every setting has its default
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                println!("{}: OK (not created yet, using defaults)", path.display());
+                return Ok(());
+            }
+            Err(e) => return Err(e).wrap_err_with(|| format!("reading {}", path.display())),
+        };
+        let problems = Self::check(&contents);
+        for problem in &problems {
+            match problem.line {
+                Some(line) => {
+                    println!("{}:{line}: {}", path.display(), problem.message);
+                    let text = contents.lines().nth(line - 1).unwrap_or_default();
+                    println!("{line:>5} | {text}");
+                }
+                None => println!("{}: {}", path.display(), problem.message),
+            }
+        }
+        if problems.is_empty() {
+            println!("{}: OK", path.display());
+            return Ok(());
+        }
+        // the problems are the report, an error trace would only bury them
+        std::process::exit(1);
+    }
+
+    /// Binance API keys from `BINANCE_API_KEY` and `BINANCE_SECRET_KEY`, or
+    /// else from [`Config::credentials_file`]. Incomplete or unreadable keys
+    /// are warned about and mean anonymous access.
+    pub fn credentials(&self) -> Option<Credentials> {
+        let api_key = std::env::var("BINANCE_API_KEY")
+            .ok()
+            .filter(|x| !x.is_empty());
+        let secret_key = std::env::var("BINANCE_SECRET_KEY")
+            .ok()
+            .filter(|x| !x.is_empty());
+        match (api_key, secret_key) {
+            (Some(api_key), Some(secret_key)) => {
+                return Some(Credentials::new(api_key, secret_key))
+            }
+            (None, None) => {}
+            _ => {
+                log::warn!(
+                    "Set both BINANCE_API_KEY and BINANCE_SECRET_KEY, using anonymous access"
+                );
+                return None;
+            }
+        }
+        let path = self.credentials_file.as_ref()?;
+        let file = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| {
+                serde_json::from_str::<CredentialsFile>(&contents).map_err(|e| e.to_string())
+            });
+        match file {
+            Ok(file) => Some(Credentials::new(file.api_key, file.secret_key)),
+            Err(e) => {
+                // the error never includes the key itself
+                log::warn!(
+                    "Ignoring credentials file {}: {}, using anonymous access",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    /// The base asset `query` is an alias for, uppercased like a search.
+    pub fn alias(&self, query: &str) -> Option<String> {
+        self.aliases
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(query.trim()))
+            .map(|(_, base)| base.trim().to_uppercase())
+    }
+
+    /// Write the config back to [`Config::path`], creating its directory.
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if self.load_failed {
+            return Err(io::Error::other(format!(
+                "{} could not be loaded, fix it (see --check-config) so it is not overwritten",
+                path.display()
+            )));
+        }
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents = serde_json::to_string_pretty(self)?;
+        fs::write(path, contents + "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn navigation_schemes_map_onto_arrows() {
+        use KeyCode::{Char, Down, Null};
+        assert_eq!(Navigation::Both.translate(Char('j')), Down);
+        assert_eq!(Navigation::Both.translate(Down), Down);
+        assert_eq!(Navigation::Vim.translate(Char('j')), Down);
+        assert_eq!(Navigation::Vim.translate(Down), Null);
+        assert_eq!(Navigation::Arrows.translate(Char('j')), Char('j'));
+        assert_eq!(Navigation::Arrows.translate(Down), Down);
+        assert_eq!(Navigation::Vim.translate(Char('q')), Char('q'));
+    }
+
+    #[test]
+    fn a_broken_file_is_never_saved_over() {
+        let dir = std::env::temp_dir().join(format!("coinmarket-config-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE);
+        fs::write(&path, "{\"refresh_interval\": 5,").unwrap();
+        let config = Config::load_from(&path);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(config.load_failed);
+        assert!(config.save().is_err());
+
+        assert!(!Config::load_from(&path).load_failed);
+    }
+
+    #[test]
+    fn alert_targets_take_their_side_from_the_price() {
+        use AlertDirection::{Above, Below};
+        assert_eq!(
+            PriceAlert::parse_target("70000", Some(65000.0)),
+            Ok((70000.0, Above))
+        );
+        assert_eq!(
+            PriceAlert::parse_target("60000", Some(65000.0)),
+            Ok((60000.0, Below))
+        );
+        assert_eq!(
+            PriceAlert::parse_target(" <70000", Some(65000.0)),
+            Ok((70000.0, Below))
+        );
+        assert_eq!(PriceAlert::parse_target("> 0.5", None), Ok((0.5, Above)));
+        for invalid in ["", ">", "abc", "-5", "0", "inf"] {
+            assert!(
+                PriceAlert::parse_target(invalid, None).is_err(),
+                "{invalid}"
+            );
+        }
+        let alert = PriceAlert {
+            symbol: "BTCUSDT".to_owned(),
+            target: 60000.0,
+            direction: Below,
+            triggered: false,
+        };
+        assert!(alert.reached(60000.0));
+        assert!(!alert.reached(60000.5));
+    }
+
+    #[test]
+    fn check_reports_every_problem_with_its_line() {
+        let contents = "{\n  \"theme\": \"pink\",\n  \"row_height\": 0,\n  \"navigation\": \"emacs\",\n  \"colour\": 1,\n  \"precision\": 2\n}";
+        let lines = Config::check(contents)
+            .into_iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, [Some(2), Some(3), Some(4), Some(5)]);
+        let broken = Config::check("{\n  \"theme\": \"red\"\n  \"precision\": 2\n}");
+        assert_eq!(broken.len(), 1);
+        assert_eq!(broken[0].line, Some(3));
+    }
+
+    #[test]
+    fn each_flash_color_is_checked_on_its_own() {
+        let contents = "{\n  \"flash_up_color\": \"green\",\n  \"flash_down_color\": \"nope\"\n}";
+        let problems = Config::check(contents);
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(3));
+        assert!(problems[0].message.starts_with("flash_down_color:"));
+    }
+}
@@ -2,7 +2,7 @@ use std::panic;
 
 use color_eyre::{config::HookBuilder, eyre};
 
-use crate::tui;
+use crate::{supervisor, tui};
 
 /// This replaces the standard color_eyre panic and error hooks with hooks that
 /// restore the terminal before printing the panic or error.
@@ -12,6 +12,11 @@ pub fn install_hooks() -> color_eyre::Result<()> {
     // convert from a color_eyre PanicHook to a standard panic hook
     let panic_hook = panic_hook.into_panic_hook();
     panic::set_hook(Box::new(move |panic_info| {
+        // the supervisor restarts the task, so the TUI keeps running
+        if let Some(task) = supervisor::current_task() {
+            log::error!("Background task {task} panicked: {panic_info}");
+            return;
+        }
         tui::Tui::exit_from_hook().unwrap();
         panic_hook(panic_info);
     }));
@@ -0,0 +1,27 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use coinmarket::MarketRow;
+
+use crate::timezone::TimeZone;
+
+/// A file name in the current directory with `extension`, named after the
+/// current time in `timezone` so it does not clash with earlier exports.
+pub fn default_path(extension: &str, timezone: TimeZone) -> PathBuf {
+    let stamp = timezone.now("%Y%m%d-%H%M%S");
+    PathBuf::from(format!("coinmarket-{stamp}.{extension}"))
+}
+
+/// Write `rows` as CSV with a header line.
+pub fn write_csv(path: &Path, rows: &[&MarketRow]) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    writeln!(out, "symbol,price,change_pct")?;
+    for row in rows {
+        let change = row.change_pct.map(|c| c.to_string()).unwrap_or_default();
+        writeln!(out, "{},{},{}", row.symbol, row.price, change)?;
+    }
+    out.flush()
+}
@@ -0,0 +1,87 @@
+use std::{collections::BTreeSet, fs, path::Path};
+
+use color_eyre::eyre::{Result, WrapErr};
+
+use crate::config::Config;
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+}
+
+/// Write the favorites to `path`, as a JSON array for `.json` files and one
+/// symbol per line otherwise.
+pub fn export(favorites: &BTreeSet<String>, path: &Path) -> Result<()> {
+    let contents = if is_json(path) {
+        serde_json::to_string_pretty(favorites)? + "\n"
+    } else {
+        favorites
+            .iter()
+            .map(|symbol| format!("{symbol}\n"))
+            .collect()
+    };
+    fs::write(path, contents).wrap_err_with(|| format!("writing {}", path.display()))
+}
+
+/// Read favorites written by [`export`] (or by hand). Blank lines and `#`
+/// comments are skipped in the plaintext format.
+pub fn read(path: &Path) -> Result<BTreeSet<String>> {
+    let contents =
+        fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+    let symbols: Vec<String> = if is_json(path) || contents.trim_start().starts_with('[') {
+        serde_json::from_str(&contents).wrap_err_with(|| format!("parsing {}", path.display()))?
+    } else {
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_owned)
+            .collect()
+    };
+    Ok(symbols
+        .into_iter()
+        .map(|symbol| symbol.to_uppercase())
+        .collect())
+}
+
+/// Merge the favorites in `path` into the config and save it. Symbols the
+/// exchange does not list are still imported (they may list later) but are
+/// reported.
+pub async fn import(config: &mut Config, path: &Path) -> Result<()> {
+    let imported = read(path)?;
+    match coinmarket::list_symbols(config.credentials().as_ref()).await {
+        Ok(known) => {
+            for symbol in imported.iter().filter(|symbol| !known.contains(*symbol)) {
+                eprintln!("warning: {symbol} is not listed on Binance, importing anyway");
+            }
+        }
+        Err(e) => eprintln!("warning: could not validate symbols: {e}"),
+    }
+    let before = config.favorites.len();
+    config.favorites.extend(imported);
+    config.save()?;
+    println!(
+        "Imported {} new favorites ({} total)",
+        config.favorites.len() - before,
+        config.favorites.len()
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_then_read_round_trips() {
+        let favorites = BTreeSet::from(["BTCUSDT".to_owned(), "ETHBTC".to_owned()]);
+        let dir = std::env::temp_dir().join(format!("coinmarket-favorites-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["favorites.json", "favorites.txt"] {
+            let path = dir.join(name);
+            export(&favorites, &path).unwrap();
+            assert_eq!(read(&path).unwrap(), favorites, "{name}");
+        }
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
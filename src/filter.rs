@@ -0,0 +1,309 @@
+use std::{fmt, ops::Range};
+
+use crate::MarketRow;
+
+/// A row field that can appear on the left-hand side of a comparison.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Field {
+    Price,
+    ChangePercent,
+}
+
+impl Field {
+    fn value(self, row: &MarketRow) -> Option<f64> {
+        match self {
+            Field::Price => Some(row.price),
+            Field::ChangePercent => row.change_pct,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Op {
+    Gt,
+    Ge,
+    Lt,
+    Le,
+    Eq,
+    Ne,
+}
+
+impl Op {
+    fn apply(self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Op::Gt => lhs > rhs,
+            Op::Ge => lhs >= rhs,
+            Op::Lt => lhs < rhs,
+            Op::Le => lhs <= rhs,
+            Op::Eq => lhs == rhs,
+            Op::Ne => lhs != rhs,
+        }
+    }
+}
+
+/// A parsed watch expression such as `price > 100 and change% < -5`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Compare(Field, Op, f64),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    /// Whether `row` satisfies the expression. Comparisons against a value the
+    /// row does not have (e.g. a missing 24h change) never match.
+    pub fn matches(&self, row: &MarketRow) -> bool {
+        match self {
+            Expr::Compare(field, op, rhs) => {
+                field.value(row).is_some_and(|lhs| op.apply(lhs, *rhs))
+            }
+            Expr::And(lhs, rhs) => lhs.matches(row) && rhs.matches(row),
+            Expr::Or(lhs, rhs) => lhs.matches(row) || rhs.matches(row),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Field(Field),
+    Op(Op),
+    Number(f64),
+    And,
+    Or,
+    LParen,
+    RParen,
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+    while let Some(&(start, c)) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::LParen);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::RParen);
+            }
+            '>' | '<' | '=' | '!' => {
+                chars.next();
+                let eq = chars.next_if(|&(_, c)| c == '=').is_some();
+                let op = match (c, eq) {
+                    ('>', false) => Op::Gt,
+                    ('>', true) => Op::Ge,
+                    ('<', false) => Op::Lt,
+                    ('<', true) => Op::Le,
+                    ('=', _) => Op::Eq,
+                    ('!', true) => Op::Ne,
+                    _ => return Err(ParseError(format!("unexpected '{c}'"))),
+                };
+                tokens.push(Token::Op(op));
+            }
+            c if c.is_ascii_digit() || c == '-' || c == '+' || c == '.' => {
+                let mut end = start;
+                while let Some((i, c)) = chars.next_if(|&(i, c)| {
+                    c.is_ascii_digit() || c == '.' || (i == start && (c == '-' || c == '+'))
+                }) {
+                    end = i + c.len_utf8();
+                }
+                let text = &input[start..end];
+                let number = text
+                    .parse()
+                    .map_err(|_| ParseError(format!("invalid number '{text}'")))?;
+                tokens.push(Token::Number(number));
+            }
+            c if c.is_alphabetic() => {
+                let mut end = start;
+                while let Some((i, c)) = chars.next_if(|&(_, c)| c.is_alphanumeric() || c == '%') {
+                    end = i + c.len_utf8();
+                }
+                let word = &input[start..end];
+                let token = match word.to_ascii_lowercase().as_str() {
+                    "price" => Token::Field(Field::Price),
+                    "change%" | "change" => Token::Field(Field::ChangePercent),
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    _ => return Err(ParseError(format!("unknown word '{word}'"))),
+                };
+                tokens.push(token);
+            }
+            _ => return Err(ParseError(format!("unexpected '{c}'"))),
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn or_expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.and_expr()?;
+        while self.peek() == Some(&Token::Or) {
+            self.next();
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.and_expr()?));
+        }
+        Ok(lhs)
+    }
+
+    fn and_expr(&mut self) -> Result<Expr, ParseError> {
+        let mut lhs = self.comparison()?;
+        while self.peek() == Some(&Token::And) {
+            self.next();
+            lhs = Expr::And(Box::new(lhs), Box::new(self.comparison()?));
+        }
+        Ok(lhs)
+    }
+
+    fn comparison(&mut self) -> Result<Expr, ParseError> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.or_expr()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(expr),
+                    _ => Err(ParseError("expected ')'".into())),
+                }
+            }
+            Some(Token::Field(field)) => {
+                let Some(Token::Op(op)) = self.next() else {
+                    return Err(ParseError("expected a comparison after field".into()));
+                };
+                let Some(Token::Number(value)) = self.next() else {
+                    return Err(ParseError("expected a number after comparison".into()));
+                };
+                Ok(Expr::Compare(field, op, value))
+            }
+            Some(_) => Err(ParseError("expected 'price' or 'change%'".into())),
+            None => Err(ParseError("unexpected end of expression".into())),
+        }
+    }
+}
+
+/// Parse a watch expression over `price` and `change%` using comparisons
+/// combined with `and`/`or` (and parentheses).
+pub fn parse(input: &str) -> Result<Expr, ParseError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        pos: 0,
+    };
+    let expr = parser.or_expr()?;
+    if parser.pos < parser.tokens.len() {
+        return Err(ParseError("unexpected trailing input".into()));
+    }
+    Ok(expr)
+}
+
+/// Parse a price band: `100-500` for prices from 100 to 500 inclusive, or
+/// an open end such as `>1000`, `>=1000`, `<0.01` or `<=0.01`. Bounds are
+/// plain decimal numbers, so `1e3`, `inf` and negative prices are rejected.
+pub fn parse_price_range(input: &str) -> Result<Expr, ParseError> {
+    let number = |text: &str| {
+        let text = text.trim();
+        let valid = !text.is_empty()
+            && text.chars().all(|c| c.is_ascii_digit() || c == '.')
+            && text.chars().filter(|c| *c == '.').count() <= 1
+            && text != ".";
+        valid
+            .then(|| text.parse::<f64>().ok())
+            .flatten()
+            .ok_or_else(|| ParseError(format!("invalid price '{text}'")))
+    };
+    let input = input.trim();
+    let open = [(">=", Op::Ge), ("<=", Op::Le), (">", Op::Gt), ("<", Op::Lt)];
+    if let Some((rest, op)) = open
+        .iter()
+        .find_map(|(prefix, op)| Some((input.strip_prefix(prefix)?, *op)))
+    {
+        return Ok(Expr::Compare(Field::Price, op, number(rest)?));
+    }
+    let Some((low, high)) = input.split_once('-') else {
+        return Err(ParseError(
+            "expected a range such as 100-500, >1000 or <0.01".into(),
+        ));
+    };
+    let (low, high) = (number(low)?, number(high)?);
+    if low > high {
+        return Err(ParseError(format!("{low} is above {high}")));
+    }
+    Ok(Expr::And(
+        Box::new(Expr::Compare(Field::Price, Op::Ge, low)),
+        Box::new(Expr::Compare(Field::Price, Op::Le, high)),
+    ))
+}
+
+/// Byte ranges of `text` that match a search for `query`, ignoring case:
+/// the first occurrence of `query`, or else the characters of `query` in
+/// order anywhere in `text`. Empty when `text` does not match.
+pub fn match_ranges(text: &str, query: &str) -> Vec<Range<usize>> {
+    let query = query.trim().chars().collect::<Vec<_>>();
+    if query.is_empty() {
+        return Vec::new();
+    }
+    let same = |a: char, b: char| a.to_lowercase().eq(b.to_lowercase());
+    for (start, _) in text.char_indices() {
+        let mut chars = text[start..].char_indices();
+        let matched = query
+            .iter()
+            .all(|q| chars.next().is_some_and(|(_, c)| same(c, *q)));
+        if matched {
+            let end = chars.next().map_or(text.len(), |(i, _)| start + i);
+            return std::iter::once(start..end).collect();
+        }
+    }
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    let mut query = query.iter().peekable();
+    for (i, c) in text.char_indices() {
+        if query.next_if(|q| same(c, **q)).is_none() {
+            continue;
+        }
+        let end = i + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.end == i => last.end = end,
+            _ => ranges.push(i..end),
+        }
+    }
+    if query.peek().is_some() {
+        return Vec::new();
+    }
+    ranges
+}
+
+/// The rows matching `filter`, or all of them when there is no filter.
+pub fn filter_rows(rows: &[MarketRow], filter: Option<&Expr>) -> Vec<MarketRow> {
+    match filter {
+        Some(filter) => rows
+            .iter()
+            .filter(|row| filter.matches(row))
+            .cloned()
+            .collect(),
+        None => rows.to_vec(),
+    }
+}
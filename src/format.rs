@@ -0,0 +1,179 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Decimal and digit-grouping separators for a locale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NumberFormat {
+    pub decimal: char,
+    pub grouping: char,
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::US
+    }
+}
+
+impl NumberFormat {
+    pub const US: Self = Self {
+        decimal: '.',
+        grouping: ',',
+    };
+
+    /// Look up the separators for a locale tag such as `de-DE`, `fr_FR.UTF-8`
+    /// or `en`. Returns `None` for tags we do not recognise.
+    pub fn from_locale(tag: &str) -> Option<Self> {
+        let tag = tag.split(['.', '@']).next().unwrap_or_default();
+        if tag.is_empty() || tag == "C" || tag == "POSIX" {
+            return Some(Self::US);
+        }
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next()?.to_ascii_lowercase();
+        let region = parts.next().map(str::to_ascii_uppercase);
+
+        let (decimal, grouping) = match (language.as_str(), region.as_deref()) {
+            ("de" | "it", Some("CH")) => ('.', '\''),
+            ("pt", Some("PT")) => (',', ' '),
+            ("en" | "ja" | "zh" | "ko" | "th" | "he" | "hi", _) => ('.', ','),
+            (
+                "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+                | "sr" | "vi",
+                _,
+            ) => (',', '.'),
+            (
+                "fr" | "ru" | "uk" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "hu" | "bg"
+                | "lt" | "lv" | "et",
+                _,
+            ) => (',', ' '),
+            _ => return None,
+        };
+        Some(Self { decimal, grouping })
+    }
+
+    /// Separators for the system locale (`LC_ALL`, `LC_NUMERIC`, then `LANG`),
+    /// or US formatting when none of them is set to a known locale.
+    pub fn system() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_locale(&value))
+            .unwrap_or_default()
+    }
+
+    /// Resolve the configured locale, warning and falling back to the system
+    /// locale when it is not recognised.
+    pub fn resolve(locale: Option<&str>) -> Self {
+        match locale {
+            Some(tag) => Self::from_locale(tag).unwrap_or_else(|| {
+                log::warn!("Unknown locale {tag:?}, using the system locale");
+                Self::system()
+            }),
+            None => Self::system(),
+        }
+    }
+}
+
+/// Format a price with the separators of `format`, grouping the integer part
+/// in thousands.
+pub fn format_price(price: f64, format: &NumberFormat) -> String {
+    group(price, &price.abs().to_string(), format)
+}
+
+/// Like [`format_price`] but always with `decimals` fraction digits.
+pub fn format_price_fixed(price: f64, decimals: usize, format: &NumberFormat) -> String {
+    group(price, &format!("{:.decimals$}", price.abs()), format)
+}
+
+/// Write the unsigned digits `raw` of `price` with the separators of `format`.
+fn group(price: f64, raw: &str, format: &NumberFormat) -> String {
+    let (integer, fraction) = match raw.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (raw, None),
+    };
+
+    let mut out = String::with_capacity(raw.len() + integer.len() / 3 + 1);
+    if price.is_sign_negative() && raw.bytes().any(|b| b.is_ascii_digit() && b != b'0') {
+        out.push('-');
+    }
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i) % 3 == 0 {
+            out.push(format.grouping);
+        }
+        out.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        out.push(format.decimal);
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// Shorten `s` to at most `width` terminal columns, ending in `…` when
+/// anything was cut. Cuts fall between graphemes, so wide and combined
+/// characters are never split.
+pub fn truncate_display(s: &str, width: usize) -> String {
+    if s.width() <= width {
+        return s.to_owned();
+    }
+    let mut out = String::new();
+    let mut used = 0;
+    for grapheme in s.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        // keep one column for the ellipsis
+        if used + grapheme_width + 1 > width {
+            break;
+        }
+        out.push_str(grapheme);
+        used += grapheme_width;
+    }
+    if width > 0 {
+        out.push('…');
+    }
+    out
+}
+
+/// How prices are written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Notation {
+    /// Plain digits with grouped thousands, see [`format_price`].
+    #[default]
+    Decimal,
+    /// Four significant digits and a signed two digit exponent, so every
+    /// price has the same width, e.g. `1.234e-08` or `6.500e+04`.
+    Scientific,
+}
+
+impl Notation {
+    pub fn toggle(self) -> Self {
+        match self {
+            Self::Decimal => Self::Scientific,
+            Self::Scientific => Self::Decimal,
+        }
+    }
+
+    /// Format `price` in this notation with the separators of `format`.
+    pub fn format(self, price: f64, format: &NumberFormat) -> String {
+        match self {
+            Self::Decimal => format_price(price, format),
+            Self::Scientific => format_scientific(price, format),
+        }
+    }
+}
+
+/// Format a price in [`Notation::Scientific`].
+pub fn format_scientific(price: f64, format: &NumberFormat) -> String {
+    let raw = format!("{price:.3e}");
+    let Some((mantissa, exponent)) = raw.split_once('e') else {
+        // inf and NaN have no exponent
+        return raw;
+    };
+    let (sign, digits) = match exponent.strip_prefix('-') {
+        Some(digits) => ('-', digits),
+        None => ('+', exponent),
+    };
+    format!(
+        "{}e{sign}{digits:0>2}",
+        mantissa.replace('.', &format.decimal.to_string())
+    )
+}
@@ -0,0 +1,95 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::config::Config;
+
+const HISTORY_FILE: &str = "history.json";
+/// Largest history file written; the oldest samples are dropped to fit.
+pub const MAX_HISTORY_BYTES: usize = 1024 * 1024;
+
+/// `(unix seconds, price)` samples per symbol, oldest first.
+pub type Histories = HashMap<String, VecDeque<(f64, f64)>>;
+
+/// The history file, kept next to the config file.
+pub fn path() -> Option<PathBuf> {
+    Some(Config::path()?.parent()?.join(HISTORY_FILE))
+}
+
+/// Read the histories saved by [`save`], keeping the newest `len` samples of
+/// each. A missing or unreadable file gives none.
+pub fn load(path: &Path, len: usize) -> Histories {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Histories::new();
+    };
+    match serde_json::from_str::<Histories>(&contents) {
+        Ok(mut histories) => {
+            for samples in histories.values_mut() {
+                let excess = samples.len().saturating_sub(len);
+                samples.drain(..excess);
+            }
+            histories
+        }
+        Err(e) => {
+            log::warn!("Ignoring the price history in {}: {e}", path.display());
+            Histories::new()
+        }
+    }
+}
+
+/// Serialize `histories`, dropping the oldest samples of every symbol until
+/// the result fits in `max_bytes`.
+pub fn encode<'a>(
+    histories: impl IntoIterator<Item = (&'a String, &'a VecDeque<(f64, f64)>)>,
+    max_bytes: usize,
+) -> serde_json::Result<String> {
+    // sorted so the file does not churn between saves
+    let mut histories = histories
+        .into_iter()
+        .map(|(symbol, samples)| (symbol, samples.iter().copied().collect::<Vec<_>>()))
+        .collect::<BTreeMap<_, _>>();
+    loop {
+        let contents = serde_json::to_string(&histories)?;
+        if contents.len() <= max_bytes || histories.values().all(Vec::is_empty) {
+            return Ok(contents);
+        }
+        for samples in histories.values_mut() {
+            let drop = samples.len().div_ceil(10);
+            samples.drain(..drop);
+        }
+    }
+}
+
+/// Write `histories` to `path` within [`MAX_HISTORY_BYTES`].
+pub fn save<'a>(
+    path: &Path,
+    histories: impl IntoIterator<Item = (&'a String, &'a VecDeque<(f64, f64)>)>,
+) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, encode(histories, MAX_HISTORY_BYTES)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encoding_drops_the_oldest_samples_to_fit() {
+        let samples = (0..1000)
+            .map(|i| (f64::from(i), 65000.5))
+            .collect::<VecDeque<_>>();
+        let symbol = "BTCUSDT".to_owned();
+        let full = encode([(&symbol, &samples)], usize::MAX).unwrap();
+        let capped = encode([(&symbol, &samples)], full.len() / 2).unwrap();
+        assert!(capped.len() <= full.len() / 2);
+
+        let decoded: HashMap<String, Vec<(f64, f64)>> = serde_json::from_str(&capped).unwrap();
+        let kept = &decoded[&symbol];
+        assert!(!kept.is_empty());
+        assert_eq!(kept.last(), Some(&(999.0, 65000.5)));
+    }
+}
@@ -0,0 +1,21 @@
+//! Binance spot market data as used by the `coinmarket` TUI.
+//!
+//! [`market_data`] fetches prices and 24h changes into [`MarketRow`]s,
+//! [`filter`] narrows them down with watch expressions such as
+//! `price > 100 and change% < -5`, [`sort`] orders them by a column,
+//! [`format`] renders prices for a locale and [`compare`] joins two saved
+//! sets of rows.
+
+pub mod compare;
+pub mod filter;
+pub mod format;
+mod market;
+pub mod sort;
+
+pub use market::{
+    api_usage, base_prices, describe_error, exact_pair, filter_symbols, find_pairs, list_symbols,
+    market_data, market_prices, pair_prices, pair_stats, price_precisions, quote_of,
+    requests_in_flight, reset_api_usage, server_time_offset, stats_24h, tick_decimals,
+    week_ago_close, window_open, ApiUsage, Credentials, InvalidCredentials, MarketRow, NoPairs,
+    RateLimited, Stats, Timeframe, ALL_QUOTES,
+};
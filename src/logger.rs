@@ -0,0 +1,99 @@
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use chrono::{DateTime, Local};
+use color_eyre::eyre::Result;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+/// Number of log records kept for the log view.
+const CAPACITY: usize = 200;
+
+/// Whether the TUI owns the terminal, see [`capture`].
+static CAPTURING: AtomicBool = AtomicBool::new(false);
+
+/// A captured log record.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub time: DateTime<Local>,
+    pub level: Level,
+    pub message: String,
+}
+
+/// The most recent [`LogEntry`]s, oldest first, shared with the log view.
+#[derive(Clone, Debug, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<LogEntry>>>);
+
+impl LogBuffer {
+    fn push(&self, entry: LogEntry) {
+        let mut entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if entries.len() == CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// A copy of the buffered entries, oldest first.
+    pub fn entries(&self) -> Vec<LogEntry> {
+        let entries = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        entries.iter().cloned().collect()
+    }
+}
+
+/// Keep records in the buffer only, or forward them to `env_logger` again.
+/// `env_logger` writes to stderr, which the TUI draws on, so records are
+/// captured from `tui.enter()` until `tui.exit()`.
+pub fn capture(on: bool) {
+    CAPTURING.store(on, Ordering::Relaxed);
+}
+
+/// Forwards records to `env_logger`, unless [`capture`]d, while keeping a
+/// copy in a [`LogBuffer`].
+struct Logger {
+    inner: env_logger::Logger,
+    buffer: LogBuffer,
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.matches(record) {
+            return;
+        }
+        self.buffer.push(LogEntry {
+            time: Local::now(),
+            level: record.level(),
+            message: record.args().to_string(),
+        });
+        if !CAPTURING.load(Ordering::Relaxed) {
+            self.inner.log(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the global logger (info by default, `RUST_LOG` overrides) and
+/// return the buffer it records into.
+pub fn init() -> Result<LogBuffer> {
+    let inner = env_logger::Builder::new()
+        .filter_level(LevelFilter::Info)
+        .parse_default_env()
+        .build();
+    let buffer = LogBuffer::default();
+    log::set_max_level(inner.filter());
+    log::set_boxed_logger(Box::new(Logger {
+        inner,
+        buffer: buffer.clone(),
+    }))?;
+    Ok(buffer)
+}
@@ -1,29 +1,152 @@
 // ANCHOR: all
+mod browser;
+mod cache;
+mod cli;
+mod clipboard;
+mod config;
 mod errors;
+mod export;
+mod favorites;
+mod history;
+mod logger;
+mod metrics;
+mod palette;
+mod server;
+mod settings;
+mod snapshot;
+mod supervisor;
+mod timezone;
 mod tui;
+mod whats_new;
 
-use binance::api::*;
-use binance::market::*;
-use binance::rest_model::SymbolPrice;
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet, VecDeque},
+    io::Write,
+    path::Path,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use cache::TtlCache;
+use chrono::{DateTime, Local};
+use cli::{Args, Command};
+use coinmarket::{
+    compare::{compare, sort_comparisons, Comparison},
+    exact_pair,
+    filter::{self, Expr},
+    format::{format_price, format_price_fixed, truncate_display, Notation, NumberFormat},
+    market_prices, pair_stats, quote_of,
+    sort::{sort_by_optional, sort_market_data, SortMode},
+    stats_24h, Credentials, InvalidCredentials, MarketRow, NoPairs, RateLimited, Timeframe,
+    ALL_QUOTES,
+};
 use color_eyre::eyre::Result;
-use crossterm::event::KeyCode::*;
-use env_logger::Builder;
-use log::LevelFilter;
+use config::{Config, Navigation, PriceAlert, QuickFilter, SelectionStyle, ViewMode};
+use crossterm::event::{KeyCode::*, KeyModifiers, MouseButton, MouseEventKind};
+use logger::LogBuffer;
+use metrics::Metrics;
+use palette::Palette;
+use rand::Rng;
 use ratatui::{prelude::*, style::palette::tailwind, style::Modifier, widgets::*};
-use tokio::sync::mpsc::{self};
+use server::SharedRows;
+use settings::{Field, Settings};
+use snapshot::Snapshot;
+use supervisor::Supervisor;
+use timezone::TimeZone;
+use tokio::sync::mpsc::{self, UnboundedSender};
+use tokio_util::sync::CancellationToken;
 use tui::Event;
 use tui_textarea::{Input, Key, TextArea};
 use unicode_width::UnicodeWidthStr;
 
-const ITEM_HEIGHT: usize = 4;
+const DEFAULT_QUOTE_ASSET: &str = "USDT";
+/// How long a fetched week-ago close is reused before it is fetched again.
+const WEEK_AGO_TTL: Duration = Duration::from_secs(60 * 60);
+/// Symbols kept by each per-symbol cache, more than are usually listed for
+/// one quote asset.
+const SYMBOL_CACHE_CAPACITY: usize = 1024;
+/// Window opens fetched at once. New ones are only started on a tick, so
+/// this also bounds them to 8 a second, 960 of Binance's 6000 weight a
+/// minute, while a long list sorted by the change fills in.
+const MAX_WINDOW_OPENS_IN_FLIGHT: usize = 8;
+/// How long window opens are left alone after Binance throttled one.
+const WINDOW_OPEN_BACKOFF: Duration = Duration::from_secs(60);
+/// How long tick sizes from exchange info are used before they are fetched
+/// again; they rarely change.
+const SYMBOL_PRECISION_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+/// Clock skew in milliseconds worth warning about, well before signed
+/// requests fall outside Binance's default 5s receive window.
+const CLOCK_SKEW_WARNING: i64 = 1000;
+/// Prices kept per symbol for the chart pane when `history_len` is unset.
+const DEFAULT_HISTORY_LEN: usize = 240;
+/// Height of the chart pane, borders included.
+const CHART_HEIGHT: u16 = 12;
+const DEFAULT_ROW_HEIGHT: u16 = 3;
+const DEFAULT_ROW_PADDING: u16 = 1;
+/// Ticks per second; `Action::Tick` doubles as the auto-refresh clock.
+const TICK_RATE: f64 = 1.0;
+/// Seconds each row stays selected in ticker mode when `ticker_interval` is
+/// unset.
+const DEFAULT_TICKER_INTERVAL: u32 = 3;
+/// Seconds the ticker waits after the selection is moved by hand.
+const TICKER_MANUAL_PAUSE: u32 = 15;
+/// Minimum time between fetches triggered by regaining focus, in seconds.
+const FOCUS_REFRESH_DEBOUNCE: u32 = 5;
+/// Auto-refresh interval used when `refresh_interval` is not configured.
+const DEFAULT_REFRESH_INTERVAL: u32 = 30;
+/// Longest back-off in seconds for rate limited 24h statistics.
+const MAX_STATS_INTERVAL: u32 = 15 * 60;
+/// Rows shown by the movers view.
+const TOP_MOVERS: usize = 20;
+/// Rows built beyond the visible ones when `render_buffer` is not configured.
+const DEFAULT_RENDER_BUFFER: usize = 10;
+/// Seconds a footer message stays up when `flash_duration` is not configured.
+const DEFAULT_FLASH_DURATION: u32 = 5;
+/// Seconds a changed price is highlighted when `change_flash_duration` is
+/// not configured.
+const DEFAULT_CHANGE_FLASH_DURATION: u32 = 2;
 const PALETTES: [tailwind::Palette; 4] = [
     tailwind::BLUE,
     tailwind::EMERALD,
     tailwind::INDIGO,
     tailwind::RED,
 ];
-const INFO_TEXT: &str =
-    "(Esc) quit | (↑) move up | (↓) move down | (→) next color | (←) previous color | (s) search coin | (r) refresh";
+/// Names of [`PALETTES`] for the `theme` setting.
+const PALETTE_NAMES: [&str; 4] = ["blue", "emerald", "indigo", "red"];
+/// Price decimals per quote asset when `precision` is not set; quotes not
+/// listed here show every digit.
+const DEFAULT_QUOTE_PRECISION: [(&str, usize); 9] = [
+    ("BTC", 8),
+    ("ETH", 8),
+    ("BNB", 8),
+    ("USDT", 4),
+    ("USDC", 4),
+    ("FDUSD", 4),
+    ("TUSD", 4),
+    ("BUSD", 4),
+    ("EUR", 4),
+];
+/// Quote assets grouped by the stablecoin view when `stablecoins` is unset.
+const DEFAULT_STABLECOINS: [&str; 6] = ["USDT", "USDC", "FDUSD", "TUSD", "BUSD", "DAI"];
+/// Spread in percent between stablecoin prices that is highlighted when
+/// `depeg_threshold` is unset.
+const DEFAULT_DEPEG_THRESHOLD: f64 = 0.5;
+/// Largest accepted `precision`; f64 has no more significant digits.
+const MAX_PRECISION: usize = 16;
+/// Significant digits of inverted prices, whose tick size says nothing.
+const INVERSE_DIGITS: i32 = 6;
+/// Cells in a full volume bar.
+const VOLUME_BAR_WIDTH: u16 = 10;
+/// Width of the Quote column, fitting the longest known quote such as `FDUSD`.
+const QUOTE_WIDTH: u16 = 5;
+/// Last footer hint when not all of them fit.
+const LEGEND_MORE: &str = "(?) more";
+const SEARCH_TITLE: &str = "Coin Search (single line) - Enter to search";
+/// Search box input starting with this prefix is parsed as a watch expression.
+const FILTER_PREFIX: char = '=';
+/// Search box input starting with this prefix is parsed as a price band.
+const PRICE_RANGE_PREFIX: char = '$';
 
 struct TableColors {
     buffer_bg: Color,
@@ -34,6 +157,14 @@ struct TableColors {
     normal_row_color: Color,
     alt_row_color: Color,
     footer_border_color: Color,
+    header_modifier: Modifier,
+    up_style: Style,
+    down_style: Style,
+    /// Prices that just went up or down.
+    flash_up_style: Style,
+    flash_down_style: Style,
+    search_border_color: Color,
+    search_fg: Color,
 }
 
 impl TableColors {
@@ -47,55 +178,638 @@ impl TableColors {
             normal_row_color: tailwind::SLATE.c950,
             alt_row_color: tailwind::SLATE.c900,
             footer_border_color: color.c400,
+            header_modifier: Modifier::empty(),
+            up_style: Style::new().fg(tailwind::GREEN.c400),
+            down_style: Style::new().fg(tailwind::RED.c400),
+            flash_up_style: Style::new().bg(tailwind::GREEN.c800),
+            flash_down_style: Style::new().bg(tailwind::RED.c800),
+            search_border_color: Color::LightBlue,
+            search_fg: Color::Yellow,
+        }
+    }
+
+    /// Colorless variant for `--mono`/`NO_COLOR`: everything uses the
+    /// terminal's default colors and meaning is carried by modifiers.
+    const fn mono() -> Self {
+        Self {
+            buffer_bg: Color::Reset,
+            header_bg: Color::Reset,
+            header_fg: Color::Reset,
+            row_fg: Color::Reset,
+            selected_style_fg: Color::Reset,
+            normal_row_color: Color::Reset,
+            alt_row_color: Color::Reset,
+            footer_border_color: Color::Reset,
+            header_modifier: Modifier::BOLD.union(Modifier::UNDERLINED),
+            up_style: Style::new().add_modifier(Modifier::BOLD),
+            down_style: Style::new().add_modifier(Modifier::UNDERLINED),
+            flash_up_style: Style::new().add_modifier(Modifier::REVERSED),
+            flash_down_style: Style::new().add_modifier(Modifier::REVERSED),
+            search_border_color: Color::Reset,
+            search_fg: Color::Reset,
+        }
+    }
+
+    /// The theme at `color_index`, or the colorless variant when `mono`,
+    /// with the flash colors of `config`.
+    fn resolve(mono: bool, color_index: usize, config: &Config) -> Self {
+        if mono {
+            return Self::mono();
+        }
+        let mut colors = Self::new(&PALETTES[color_index]);
+        let color = |name: Option<&str>| {
+            let name = name?;
+            name.parse::<Color>()
+                .inspect_err(|_| log::warn!("Unknown color {name:?}, using the default"))
+                .ok()
+        };
+        if let Some(up) = color(config.flash_up_color.as_deref()) {
+            colors.flash_up_style = Style::new().bg(up);
+        }
+        if let Some(down) = color(config.flash_down_color.as_deref()) {
+            colors.flash_down_style = Style::new().bg(down);
+        }
+        colors
+    }
+}
+
+/// Height of each table row and the blank lines above its content.
+#[derive(Clone, Copy, Debug)]
+struct RowLayout {
+    height: u16,
+    padding: u16,
+}
+
+impl RowLayout {
+    /// Validate the configured values: rows are at least one line tall and
+    /// the padding always leaves room for the content line.
+    fn new(height: Option<u16>, padding: Option<u16>) -> Self {
+        let mut height = height.unwrap_or(DEFAULT_ROW_HEIGHT);
+        if height == 0 {
+            log::warn!("row_height must be at least 1, using 1");
+            height = 1;
+        }
+        let mut padding = padding.unwrap_or(DEFAULT_ROW_PADDING.min(height - 1));
+        if padding >= height {
+            log::warn!("row_padding {padding} does not fit in row_height {height}");
+            padding = height - 1;
+        }
+        Self { height, padding }
+    }
+
+    fn text(self, content: &str) -> Text<'static> {
+        Text::from(format!("{}{content}", "\n".repeat(self.padding.into())))
+    }
+
+    /// Like [`RowLayout::text`] for a line of styled spans.
+    fn line(self, line: Line<'static>) -> Text<'static> {
+        let mut lines = vec![Line::default(); self.padding.into()];
+        lines.push(line);
+        Text::from(lines)
+    }
+
+    /// The selection bar, drawn from the content line down.
+    fn highlight_symbol(self) -> Text<'static> {
+        let bar = " █ ";
+        Text::from(
+            (0..self.height)
+                .map(|line| Line::from(if line < self.padding { "" } else { bar }))
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Active sort column and direction.
+#[derive(Clone, Copy, Debug, Default)]
+struct SortState {
+    mode: SortMode,
+    descending: bool,
+}
+
+/// The price alerts screen, opened with `B`.
+#[derive(Default)]
+struct AlertsView<'a> {
+    /// Selection among [`Config::alerts`].
+    state: TableState,
+    /// Alert being added or edited, if any.
+    editor: Option<AlertEdit<'a>>,
+    /// Why the last edit could not be saved.
+    error: Option<String>,
+}
+
+/// A price alert being typed: a new one on `symbol`, or the one at `index`.
+struct AlertEdit<'a> {
+    index: Option<usize>,
+    symbol: String,
+    input: TextArea<'a>,
+}
+
+/// Prices of one base asset against each stablecoin, opened with `g`.
+struct StableView {
+    base: String,
+    /// `(quote, price)` in the order of the configured stablecoins.
+    prices: Vec<(String, f64)>,
+    /// Why the prices could not be fetched.
+    error: Option<String>,
+}
+
+impl StableView {
+    /// Percent difference of each price from the first one; `None` when
+    /// the first is zero, as for a pair that is not trading.
+    fn spreads(&self) -> Vec<Option<f64>> {
+        let Some((_, reference)) = self.prices.first() else {
+            return Vec::new();
+        };
+        self.prices
+            .iter()
+            .map(|(_, price)| (*reference != 0.0).then(|| (price - reference) / reference * 100.0))
+            .collect()
+    }
+}
+
+/// Two snapshots joined by symbol, shown instead of live prices.
+struct CompareView {
+    /// File names of the two snapshots.
+    names: (String, String),
+    rows: Vec<Comparison>,
+    state: TableState,
+    by_delta: bool,
+    descending: bool,
+}
+
+impl CompareView {
+    fn load(a: &Path, b: &Path) -> Result<Self> {
+        let name = |path: &Path| {
+            path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            )
+        };
+        let rows = compare(&Snapshot::load(a)?.rows, &Snapshot::load(b)?.rows);
+        Ok(Self {
+            names: (name(a), name(b)),
+            rows,
+            state: TableState::default().with_selected(0),
+            by_delta: false,
+            descending: false,
+        })
+    }
+
+    fn move_by(&mut self, delta: isize) {
+        let len = self.rows.len();
+        if len == 0 {
+            return;
         }
+        let i = self.state.selected().unwrap_or(0) as isize + delta;
+        self.state.select(Some(i.rem_euclid(len as isize) as usize));
+    }
+
+    /// Switch between sorting by symbol and by delta (largest first).
+    fn toggle_sort(&mut self) {
+        self.by_delta = !self.by_delta;
+        self.descending = self.by_delta;
+        self.sort();
+    }
+
+    fn toggle_sort_direction(&mut self) {
+        self.descending = !self.descending;
+        self.sort();
+    }
+
+    fn sort(&mut self) {
+        sort_comparisons(&mut self.rows, self.by_delta, self.descending);
+        self.state.select(Some(0));
     }
 }
 
 // App state
 struct App<'a> {
     should_quit: bool,
-    longest_item_lens: (u16, u16),
-    market_data: Vec<SymbolPrice>,
+    /// When a key was last pressed, for `inactivity_timeout`.
+    last_input: Instant,
+    /// Quit after this long without input.
+    inactivity_timeout: Option<Duration>,
+    /// Whether the selection moves down on its own, toggled with `A`.
+    ticker: bool,
+    /// Ticks between two ticker steps.
+    ticker_interval: u32,
+    /// Ticks left until the next ticker step.
+    ticker_wait: u32,
+    longest_item_lens: (u16, u16, u16, u16),
+    /// Largest 24h quote volume in `market_data`, the full volume bar.
+    max_volume: f64,
+    /// Every row of the last fetch, before the watch expression is applied.
+    rows: Vec<MarketRow>,
+    market_data: Vec<MarketRow>,
     state: TableState,
     scroll_state: ScrollbarState,
     colors: TableColors,
+    /// Whether rows alternate between two backgrounds.
+    striped: bool,
+    selection_style: SelectionStyle,
+    navigation: Navigation,
+    /// Copy of the shown rows for `--serve`.
+    served: Option<SharedRows>,
+    /// Whether moving past either end of the table wraps to the other.
+    wrap_navigation: bool,
+    /// Whether refreshes leave the table alone while the view is frozen,
+    /// see [`App::view_frozen`].
+    hold_behind_popups: bool,
     color_index: usize,
     textarea: TextArea<'a>,
     focus_textarea: bool,
     search_coin: Option<String>,
+    filter: Option<Expr>,
+    number_format: NumberFormat,
+    /// Decimal or scientific prices, toggled with `E`.
+    notation: Notation,
+    /// Whether the Price column shows 1/price, toggled with `i`.
+    inverse: bool,
+    /// Fixed decimals for prices in decimal notation, overriding
+    /// `quote_precision`.
+    precision: Option<usize>,
+    /// Default decimals per quote asset, see [`DEFAULT_QUOTE_PRECISION`].
+    quote_precision: HashMap<String, usize>,
+    /// Decimals per symbol from its exchange tick size, preferred over
+    /// `quote_precision`.
+    symbol_precision: HashMap<String, usize>,
+    /// When exchange info was last requested, unless that request failed.
+    symbol_precision_requested: Option<Instant>,
+    /// Performance timings, only collected with `--debug-metrics`.
+    metrics: Option<Metrics>,
+    /// Snapshot comparison given with `--compare`, replacing the live table.
+    compare: Option<CompareView>,
+    /// The settings screen, while it is open.
+    settings: Option<Settings<'a>>,
+    mono: bool,
+    auto_refresh: bool,
+    /// Auto-refresh interval in ticks.
+    refresh_interval: u32,
+    /// Most ticks added at random to an auto-refresh interval.
+    refresh_jitter: u32,
+    /// Ticks added to the current interval, drawn at each refresh.
+    refresh_delay: u32,
+    ticks_since_refresh: u32,
+    /// When the refresh in flight started, `None` while none is.
+    refresh_started: Option<Instant>,
+    /// Refreshes started so far; the result of one a newer refresh
+    /// superseded, e.g. for another search, is dropped.
+    refresh_generation: u64,
+    /// When a refresh last succeeded, however long ago it was attempted.
+    last_update: Instant,
+    /// When the shown rows were fetched, before `last_update` while newer
+    /// rows are held back.
+    shown_update: Instant,
+    /// Grey out the table after this long without a successful refresh.
+    max_staleness: Option<Duration>,
+    /// Whether `max_staleness` has passed, checked on each tick.
+    data_stale: bool,
+    /// Ticks since the 24h statistics were fetched, which price-only
+    /// refreshes leave untouched.
+    ticks_since_stats: u32,
+    /// Ticks between automatic statistics fetches: the refresh interval,
+    /// lengthened while Binance rate limits the statistics.
+    stats_interval: u32,
+    /// Whether the last statistics fetch failed, so the shown ones are old.
+    stats_stale: bool,
+    /// Symbols marked with Space; export and copy act on these when non-empty.
+    marked: HashSet<String>,
+    /// Transient footer message, whether a confirmation, an error or a
+    /// notice, with the ticks it stays up for.
+    flash_message: Option<(String, u32)>,
+    /// Ticks a new flash message stays up for.
+    flash_duration: u32,
+    /// Release notes shown once after an upgrade; empty once dismissed.
+    whats_new: Vec<(&'static str, &'static [&'static str])>,
+    /// Percent move of a favorite between refreshes that rings the bell.
+    move_alert: Option<f64>,
+    /// Favorites past `move_alert` on the last refresh; they ring again
+    /// only after a calmer refresh, not for every step of one big move.
+    moving: HashSet<String>,
+    /// Whether a fetch has ever succeeded; until then the startup screen is shown.
+    loaded: bool,
+    /// Recent `(unix seconds, price)` samples per symbol, one per fetch,
+    /// at most [`App::history_len`] each.
+    history: HashMap<String, VecDeque<(f64, f64)>>,
+    history_len: usize,
+    /// Symbols whose price changed in a recent fetch: whether it went up,
+    /// and when.
+    price_flashes: HashMap<String, (bool, Instant)>,
+    /// How long a changed price stays highlighted.
+    change_flash: Duration,
+    /// Quote assets of the tab bar, empty without one.
+    quote_tabs: Vec<String>,
+    /// Where the tab bar was last drawn, for clicks.
+    tabs_area: Rect,
+    /// Symbol groups from the config, bound to the keys 1 to 9.
+    quick_filters: Vec<QuickFilter>,
+    /// Index of the quick filter in use.
+    quick_filter: Option<usize>,
+    /// Which rows are shown, cycled with `V`.
+    view: ViewMode,
+    /// Selected symbol and scroll offset each view was left with.
+    view_positions: HashMap<ViewMode, (Option<String>, usize)>,
+    /// Whether only rows whose price changed in the last fetch are shown,
+    /// toggled with `m`.
+    only_moved: bool,
+    /// Whether the chart pane for the selected symbol is shown, toggled with `c`.
+    show_chart: bool,
+    /// Whether fetched rows are held back instead of shown, toggled with `p`.
+    paused: bool,
+    /// The latest rows fetched while paused. Each fetch replaces the last,
+    /// so a long pause holds a single batch.
+    held_rows: Option<Vec<MarketRow>>,
+    /// Cause of the last failed fetch while nothing has loaded yet.
+    startup_error: Option<String>,
+    /// Why the last search found no pairs, shown in place of the table.
+    empty_reason: Option<String>,
+    row_layout: RowLayout,
+    /// Symbols seen in the previous fetch, used to detect new listings;
+    /// `None` until the first fetch for the current search.
+    known_symbols: Option<HashSet<String>>,
+    /// Server minus local clock in milliseconds, measured once at startup.
+    clock_offset: Option<i64>,
+    /// Quote asset whose pairs are listed, e.g. `USDT`.
+    quote_asset: String,
+    sort: SortState,
+    /// Sort to go back to when `b` turns the volume sort off again.
+    sort_before_volume: Option<SortState>,
+    favorites: HashSet<String>,
+    /// Loaded config, written back when persisted state such as favorites changes.
+    config: Config,
+    /// Whether the 7d change column is shown (and fetched).
+    show_7d: bool,
+    /// Whether the column of per-row update times is wanted, toggled with `t`.
+    show_updated: bool,
+    /// When each symbol's price last changed between fetches.
+    updated_at: HashMap<String, DateTime<Local>>,
+    /// Close price seven days ago per symbol, with when it was fetched.
+    week_ago: TtlCache<String, f64>,
+    /// Symbols whose week-ago close is being fetched in the background.
+    week_ago_pending: HashSet<String>,
+    /// Window of the change column, cycled with `T`.
+    timeframe: Timeframe,
+    /// Price at the start of each window shorter than 24h per symbol, with
+    /// when it was fetched, see [`Timeframe::refresh_after`].
+    window_opens: HashMap<Timeframe, TtlCache<String, f64>>,
+    /// Window opens being fetched in the background.
+    window_opens_pending: HashSet<(Timeframe, String)>,
+    /// No window opens are fetched until then, after one was throttled.
+    window_opens_throttled_until: Option<Instant>,
+    /// Recenter the viewport on the selection at the next render; set when
+    /// the selection moves, so a restored offset is otherwise kept.
+    recenter: bool,
+    /// Number of table rows that fit on screen, updated on every render.
+    visible_rows: usize,
+    /// Rows built above and below the visible ones when drawing the table.
+    render_buffer: usize,
+    /// Columns shown at the last render, copied by `Y`.
+    columns: Vec<Column>,
+    /// Whether anything on screen may have changed since the last draw;
+    /// frames are skipped while this is false.
+    dirty: bool,
+    action_tx: UnboundedSender<Action>,
+    /// API keys used for requests, dropped if Binance rejects them.
+    credentials: Option<Credentials>,
+    /// Recent log records, shown in the log view.
+    log: LogBuffer,
+    /// Whether the log view is open.
+    show_log: bool,
+    /// Whether the favorites digest is open.
+    show_digest: bool,
+    /// The command palette, while open.
+    palette: Option<Palette<'a>>,
+    /// Symbol whose note is being edited, with its editor.
+    note_editor: Option<(String, TextArea<'a>)>,
+    /// Whether a note changed since loading, so the config is saved on quit.
+    notes_changed: bool,
+    /// Price of each favorite when the digest was last dismissed, or when
+    /// it was first fetched.
+    digest_baseline: HashMap<String, f64>,
+    /// When the digest was last dismissed, or the start.
+    digest_since: DateTime<Local>,
+    /// Lines scrolled down in the log view.
+    log_scroll: u16,
+    /// The stablecoin view, while it is open.
+    stable_view: Option<StableView>,
+    /// The price alerts screen, while it is open.
+    alerts_view: Option<AlertsView<'a>>,
+    /// First symbol picked with `v`, waiting for the second.
+    pair_pick: Option<String>,
+    /// Two symbols shown side by side, opened by picking a second one.
+    side_by_side: Option<(String, String)>,
+    /// Runs the background fetches.
+    supervisor: Supervisor,
+    /// Zone of the shown timestamps.
+    timezone: TimeZone,
 }
 
 impl<'a> App<'a> {
-    async fn new() -> Result<Self> {
-        let market_data = market_data(None).await.unwrap();
-        let mut textarea = TextArea::default();
-        textarea.set_block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::LightBlue))
-                .title("Coin Search - Enter to search"),
+    /// Create the app with no data; the first fetch happens from the event
+    /// loop so failures can be shown in the TUI.
+    fn new(
+        config: &Config,
+        args: &Args,
+        action_tx: UnboundedSender<Action>,
+        log: LogBuffer,
+    ) -> Self {
+        let number_format = NumberFormat::resolve(config.locale.as_deref());
+        let color_index = match config.theme.as_deref() {
+            Some(theme) => PALETTE_NAMES
+                .iter()
+                .position(|name| name.eq_ignore_ascii_case(theme))
+                .unwrap_or_else(|| {
+                    log::warn!("Unknown theme {theme:?}, using {}", PALETTE_NAMES[0]);
+                    0
+                }),
+            None => 0,
+        };
+        let colors = TableColors::resolve(args.mono, color_index, config);
+        let refresh_interval = secs_to_ticks(
+            config
+                .refresh_interval
+                .unwrap_or(DEFAULT_REFRESH_INTERVAL)
+                .max(1),
         );
-        textarea.set_style(Style::default().fg(Color::Yellow));
+        let history_len = config.history_len.unwrap_or(DEFAULT_HISTORY_LEN).max(2);
+        let mut textarea = TextArea::default();
+        textarea.set_block(search_block(SEARCH_TITLE, &colors));
+        textarea.set_style(Style::default().fg(colors.search_fg));
         textarea.set_placeholder_style(Style::default());
-        textarea.set_placeholder_text("BTC/ETH/AKT \n(only 1 coin at a time without punctuation)");
-        Ok(Self {
+        textarea.set_placeholder_text(
+            "BTC/ETH/AKT \n(only 1 coin at a time without punctuation)\n= price > 100 and change% < -5\n$100-500, $>1000 or $<0.01",
+        );
+        Self {
             state: TableState::default().with_selected(0),
-            longest_item_lens: constraint_len_calculator(&market_data),
-            scroll_state: ScrollbarState::new((market_data.len() - 1) * ITEM_HEIGHT),
-            colors: TableColors::new(&PALETTES[0]),
-            color_index: 0,
-            market_data,
+            longest_item_lens: (0, 0, 0, 0),
+            max_volume: 0.0,
+            scroll_state: ScrollbarState::new(0),
+            colors,
+            striped: config.striped.unwrap_or(true),
+            selection_style: config.selection_style.unwrap_or_default(),
+            navigation: config.navigation.unwrap_or_default(),
+            served: None,
+            wrap_navigation: config.wrap_navigation.unwrap_or(true),
+            hold_behind_popups: config.hold_behind_popups.unwrap_or(true),
+            color_index,
+            rows: Vec::new(),
+            market_data: Vec::new(),
             should_quit: false,
+            last_input: Instant::now(),
+            inactivity_timeout: config
+                .inactivity_timeout
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            ticker: false,
+            ticker_interval: secs_to_ticks(
+                config
+                    .ticker_interval
+                    .filter(|secs| *secs > 0)
+                    .unwrap_or(DEFAULT_TICKER_INTERVAL),
+            ),
+            ticker_wait: 0,
             textarea,
             focus_textarea: false,
             search_coin: None,
-        })
+            filter: None,
+            number_format,
+            notation: Notation::Decimal,
+            inverse: false,
+            precision: config.precision.map(|p| p.min(MAX_PRECISION)),
+            symbol_precision: HashMap::new(),
+            symbol_precision_requested: None,
+            quote_precision: DEFAULT_QUOTE_PRECISION
+                .iter()
+                .map(|(quote, precision)| ((*quote).to_owned(), *precision))
+                .chain(
+                    config
+                        .quote_precision
+                        .iter()
+                        .map(|(quote, precision)| (quote.to_uppercase(), *precision)),
+                )
+                .map(|(quote, precision)| (quote, precision.min(MAX_PRECISION)))
+                .collect(),
+            settings: None,
+            compare: None,
+            metrics: args.debug_metrics.then(Metrics::default),
+            mono: args.mono,
+            auto_refresh: config.refresh_interval.is_some(),
+            refresh_interval,
+            refresh_jitter: secs_to_ticks(config.refresh_jitter.unwrap_or(0)),
+            refresh_delay: 0,
+            ticks_since_refresh: 0,
+            refresh_started: None,
+            refresh_generation: 0,
+            last_update: Instant::now(),
+            shown_update: Instant::now(),
+            max_staleness: config
+                .max_staleness
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            data_stale: false,
+            ticks_since_stats: 0,
+            stats_interval: refresh_interval,
+            stats_stale: false,
+            marked: HashSet::new(),
+            flash_message: None,
+            flash_duration: secs_to_ticks(
+                config
+                    .flash_duration
+                    .unwrap_or(DEFAULT_FLASH_DURATION)
+                    .max(1),
+            ),
+            whats_new: Vec::new(),
+            move_alert: config.move_alert.filter(|percent| *percent > 0.0),
+            moving: HashSet::new(),
+            loaded: false,
+            history: load_history(config, history_len),
+            history_len,
+            price_flashes: HashMap::new(),
+            change_flash: Duration::from_secs(
+                config
+                    .change_flash_duration
+                    .unwrap_or(DEFAULT_CHANGE_FLASH_DURATION)
+                    .into(),
+            ),
+            show_chart: false,
+            only_moved: false,
+            view: config.startup_view.unwrap_or_default(),
+            view_positions: HashMap::new(),
+            quote_tabs: config
+                .quote_tabs
+                .iter()
+                .map(|quote| quote.trim().to_uppercase())
+                .collect(),
+            tabs_area: Rect::default(),
+            quick_filters: config.quick_filters.iter().take(9).cloned().collect(),
+            quick_filter: None,
+            paused: false,
+            held_rows: None,
+            startup_error: None,
+            empty_reason: None,
+            row_layout: RowLayout::new(config.row_height, config.row_padding),
+            known_symbols: None,
+            clock_offset: None,
+            quote_asset: config
+                .quote_asset
+                .as_deref()
+                .unwrap_or(DEFAULT_QUOTE_ASSET)
+                .to_uppercase(),
+            sort: config.sort.map_or_else(SortState::default, |column| {
+                let mode = SortMode::from(column);
+                SortState {
+                    mode,
+                    descending: config
+                        .sort_descending
+                        .unwrap_or_else(|| mode.default_descending()),
+                }
+            }),
+            sort_before_volume: None,
+            favorites: config.favorites.iter().cloned().collect(),
+            config: config.clone(),
+            show_7d: false,
+            show_updated: false,
+            updated_at: HashMap::new(),
+            week_ago: TtlCache::new(WEEK_AGO_TTL, SYMBOL_CACHE_CAPACITY),
+            week_ago_pending: HashSet::new(),
+            timeframe: Timeframe::default(),
+            window_opens: HashMap::new(),
+            window_opens_pending: HashSet::new(),
+            window_opens_throttled_until: None,
+            pair_pick: None,
+            side_by_side: None,
+            supervisor: Supervisor::default(),
+            render_buffer: config.render_buffer.unwrap_or(DEFAULT_RENDER_BUFFER),
+            timezone: TimeZone::resolve(config.timezone.as_deref()),
+            recenter: false,
+            visible_rows: 0,
+            columns: Vec::new(),
+            dirty: true,
+            action_tx,
+            credentials: config.credentials(),
+            log,
+            show_log: false,
+            show_digest: false,
+            palette: None,
+            note_editor: None,
+            notes_changed: false,
+            digest_baseline: HashMap::new(),
+            digest_since: Local::now(),
+            log_scroll: 0,
+            stable_view: None,
+            alerts_view: None,
+        }
     }
     pub fn next(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
-                if i >= self.market_data.len().saturating_sub(1) {
-                    0
+                let last = self.market_data.len().saturating_sub(1);
+                if i >= last {
+                    if self.wrap_navigation {
+                        0
+                    } else {
+                        last
+                    }
                 } else {
                     i + 1
                 }
@@ -103,14 +817,19 @@ impl<'a> App<'a> {
             None => 0,
         };
         self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+        self.recenter = true;
+        self.pause_ticker();
     }
 
     pub fn previous(&mut self) {
         let i = match self.state.selected() {
             Some(i) => {
                 if i == 0 {
-                    self.market_data.len().saturating_sub(1)
+                    if self.wrap_navigation {
+                        self.market_data.len().saturating_sub(1)
+                    } else {
+                        0
+                    }
                 } else {
                     i - 1
                 }
@@ -118,7 +837,61 @@ impl<'a> App<'a> {
             None => 0,
         };
         self.state.select(Some(i));
-        self.scroll_state = self.scroll_state.position(i * ITEM_HEIGHT);
+        self.recenter = true;
+        self.pause_ticker();
+    }
+
+    /// Start or stop the ticker mode.
+    pub fn toggle_ticker(&mut self) {
+        self.ticker = !self.ticker;
+        self.ticker_wait = self.ticker_interval;
+    }
+
+    /// Hold the ticker back for a while after the selection moved by hand,
+    /// whichever way it moved.
+    fn pause_ticker(&mut self) {
+        if self.ticker {
+            self.ticker_wait = secs_to_ticks(TICKER_MANUAL_PAUSE);
+        }
+    }
+
+    /// Advance the ticker by one tick, selecting the next row when due. It
+    /// always wraps, whatever `wrap_navigation` says.
+    fn tick_ticker(&mut self) {
+        if !self.ticker || self.market_data.is_empty() {
+            return;
+        }
+        self.ticker_wait = self.ticker_wait.saturating_sub(1);
+        if self.ticker_wait > 0 {
+            return;
+        }
+        self.ticker_wait = self.ticker_interval;
+        let i = self
+            .state
+            .selected()
+            .map_or(0, |i| (i + 1) % self.market_data.len());
+        self.state.select(Some(i));
+        self.recenter = true;
+    }
+
+    /// Select the next favorite after the selection in `direction` (1 or
+    /// -1), wrapping around.
+    pub fn jump_to_favorite(&mut self, direction: isize) {
+        let len = self.market_data.len();
+        let start = self.state.selected().unwrap_or(0);
+        let found = (1..=len)
+            .map(|step| {
+                (start as isize + direction * step as isize).rem_euclid(len as isize) as usize
+            })
+            .find(|&i| self.favorites.contains(&self.market_data[i].symbol));
+        match found {
+            Some(i) => {
+                self.state.select(Some(i));
+                self.recenter = true;
+                self.pause_ticker();
+            }
+            None => self.flash("No favorites shown, add one with f"),
+        }
     }
 
     pub fn next_color(&mut self) {
@@ -130,137 +903,3259 @@ impl<'a> App<'a> {
         self.color_index = (self.color_index + count - 1) % count;
     }
 
+    /// Decimals shown for prices of `symbol`: the configured precision, else
+    /// the symbol's tick size, else the default for the current quote asset.
+    pub fn effective_precision(&self, symbol: &str) -> Option<usize> {
+        self.precision
+            .or_else(|| self.symbol_precision.get(symbol).copied())
+            .or_else(|| self.quote_precision.get(self.quote_of(symbol)).copied())
+    }
+
+    /// Quote asset of `symbol`: the listed one, or the detected one when
+    /// listing every quote asset, empty if unknown.
+    fn quote_of(&self, symbol: &str) -> &str {
+        if self.quote_asset == ALL_QUOTES {
+            quote_of(symbol).unwrap_or_default()
+        } else {
+            &self.quote_asset
+        }
+    }
+
+    /// A price of `symbol` as shown in the table, honouring notation and
+    /// precision.
+    pub fn format_price(&self, symbol: &str, price: f64) -> String {
+        match (self.notation, self.effective_precision(symbol)) {
+            (Notation::Decimal, Some(decimals)) => {
+                format_price_fixed(price, decimals, &self.number_format)
+            }
+            (notation, _) => notation.format(price, &self.number_format),
+        }
+    }
+
+    /// Text of the Price column: the price, or with `inverse` the amount of
+    /// the base asset one unit of the quote buys, which has no tick size so
+    /// it gets [`INVERSE_DIGITS`] significant digits.
+    pub fn price_text(&self, symbol: &str, price: f64) -> String {
+        if !self.inverse {
+            return self.format_price(symbol, price);
+        }
+        if price <= 0.0 {
+            return "-".to_owned();
+        }
+        let inverse = 1.0 / price;
+        match self.notation {
+            Notation::Decimal => {
+                #[allow(clippy::cast_possible_truncation)]
+                let magnitude = inverse.log10().floor() as i32;
+                #[allow(clippy::cast_sign_loss)]
+                let decimals =
+                    (INVERSE_DIGITS - 1 - magnitude).clamp(0, MAX_PRECISION as i32) as usize;
+                format_price_fixed(inverse, decimals, &self.number_format)
+            }
+            notation => notation.format(inverse, &self.number_format),
+        }
+    }
+
+    /// Footer key hints for what works right now, most used first. The
+    /// footer shows as many as fit and `?` lists every key in the palette.
+    pub fn legend(&self) -> Vec<&'static str> {
+        // the arrows switch quote tabs when there are any
+        let colors = !self.mono && self.quote_tabs.is_empty();
+        [
+            ("(Esc) quit", true),
+            ("(↑) move up", true),
+            ("(↓) move down", true),
+            ("(←/→) quote tab", !self.quote_tabs.is_empty()),
+            ("(→) next color", colors),
+            ("(←) previous color", colors),
+            ("(s) search coin", true),
+            ("(r) refresh prices", true),
+            ("(R) full refresh", true),
+            ("(a) auto-refresh", true),
+            ("(A) ticker", true),
+            ("(p) pause", true),
+            ("(c) chart", true),
+            ("(t) update times", true),
+            ("(m) moved only", true),
+            ("(V) view", true),
+            ("(1-9) quick filters", !self.quick_filters.is_empty()),
+            ("(g) stablecoins", true),
+            ("(v) compare two", true),
+            ("(S) sort", true),
+            ("(d) sort direction", self.sort.mode != SortMode::None),
+            ("(b) by volume (not market cap)", true),
+            ("(w) 7d change", true),
+            ("(T) change window", true),
+            ("(L) log", true),
+            ("(D) favorites digest", !self.favorites.is_empty()),
+            ("(B) price alerts", true),
+            ("(o) open", browser::available()),
+            ("(E) scientific", true),
+            ("(i) inverse", true),
+            ("(,) settings", true),
+            ("(f) favorite", true),
+            ("([/]) prev/next favorite", !self.favorites.is_empty()),
+            ("(n) note", true),
+            ("(:) commands", true),
+            ("(space) mark", true),
+            ("(u) unmark all", !self.marked.is_empty()),
+            ("(U) reset API usage", true),
+            ("(e) export", true),
+            ("(y) copy", clipboard::available()),
+            ("(Y) copy table", true),
+            (
+                "(J) copy as JSON",
+                clipboard::available() && !self.market_data.is_empty(),
+            ),
+            ("(x) dismiss", self.flash_message.is_some()),
+            ("(0) reset view", true),
+        ]
+        .into_iter()
+        .filter_map(|(hint, enabled)| enabled.then_some(hint))
+        .collect()
+    }
+
+    /// Recompute the column widths and the volume scale after the rows or
+    /// their format changed.
+    pub fn update_lens(&mut self) {
+        self.longest_item_lens = constraint_len_calculator(
+            &self.market_data,
+            |symbol, price| self.price_text(symbol, price),
+            |row| self.change(row),
+            |column| self.header_title(column),
+        );
+        self.max_volume = self
+            .market_data
+            .iter()
+            .filter_map(|row| row.quote_volume)
+            .fold(0.0, f64::max);
+    }
+
+    /// Background of the `i`th row.
+    fn row_color(&self, i: usize) -> Color {
+        match i % 2 {
+            1 if self.striped => self.colors.alt_row_color,
+            _ => self.colors.normal_row_color,
+        }
+    }
+
+    /// Style of the selected row.
+    fn selected_style(&self) -> Style {
+        match self.selection_style {
+            // mono has no accent color to fill with
+            SelectionStyle::Background if !self.mono => Style::new()
+                .fg(self.colors.buffer_bg)
+                .bg(self.colors.selected_style_fg),
+            _ => Style::new()
+                .add_modifier(Modifier::REVERSED)
+                .fg(self.colors.selected_style_fg),
+        }
+    }
+
+    /// Show `message` in the footer for [`App::flash_duration`] ticks,
+    /// replacing any earlier one.
+    pub fn flash(&mut self, message: impl Into<String>) {
+        self.flash_message = Some((message.into(), self.flash_duration));
+    }
+
     pub fn set_colors(&mut self) {
-        self.colors = TableColors::new(&PALETTES[self.color_index]);
+        self.colors = TableColors::resolve(self.mono, self.color_index, &self.config);
     }
-}
 
-// App actions
-// ANCHOR: action_enum
-#[derive(Clone)]
-pub enum Action {
-    NEXT,
-    PREVIOUS,
-    NextColor,
-    PreviousColor,
-    SearchFocus,
-    SearchCoin(String),
-    Refresh,
-    Tick,
-    Increment,
-    Decrement,
-    Quit,
-    Render,
-    None,
-}
-// ANCHOR_END: action_enum
+    /// Mark or unmark the selected row.
+    pub fn toggle_mark(&mut self) {
+        let Some(row) = self.state.selected().and_then(|i| self.market_data.get(i)) else {
+            return;
+        };
+        if !self.marked.remove(&row.symbol) {
+            self.marked.insert(row.symbol.clone());
+        }
+    }
 
-// App ui render function
-fn ui(f: &mut Frame, app: &mut App) {
-    let rects = Layout::vertical([Constraint::Min(5), Constraint::Length(3)]).split(f.size());
+    /// Add or remove the selected symbol from the favorites and save them.
+    pub fn toggle_favorite(&mut self) {
+        let Some(row) = self.state.selected().and_then(|i| self.market_data.get(i)) else {
+            return;
+        };
+        if !self.favorites.remove(&row.symbol) {
+            self.favorites.insert(row.symbol.clone());
+        }
+        self.config.favorites = self.favorites.iter().cloned().collect();
+        if let Err(e) = self.config.save() {
+            self.flash(format!("Saving favorites failed: {e}"));
+        }
+    }
 
-    app.set_colors();
+    /// Open the command palette with an empty query.
+    pub fn open_palette(&mut self) {
+        let mut input = TextArea::default();
+        input.set_block(search_block(
+            "Command - ↑/↓ to pick, Enter to run, Esc to close",
+            &self.colors,
+        ));
+        input.set_style(Style::default().fg(self.colors.search_fg));
+        input.set_placeholder_text("Type to filter, e.g. tgch for Toggle chart");
+        self.palette = Some(Palette::new(input));
+    }
 
-    if app.focus_textarea {
-        render_textarea(f, app);
-    } else {
-        render_table(f, app, rects[0]);
+    /// Open the note editor for the selected symbol.
+    pub fn edit_note(&mut self) {
+        let Some(row) = self.state.selected().and_then(|i| self.market_data.get(i)) else {
+            return;
+        };
+        let note = self
+            .config
+            .notes
+            .get(&row.symbol)
+            .cloned()
+            .unwrap_or_default();
+        let mut editor = TextArea::new(vec![note]);
+        editor.move_cursor(tui_textarea::CursorMove::End);
+        editor.set_block(search_block(
+            &format!("Note on {} - Enter to save, Esc to cancel", row.symbol),
+            &self.colors,
+        ));
+        editor.set_style(Style::default().fg(self.colors.search_fg));
+        editor.set_placeholder_text("e.g. accumulate below 50k; empty removes the note");
+        self.note_editor = Some((row.symbol.clone(), editor));
+    }
 
-        render_scrollbar(f, app, rects[0]);
+    /// Keep the edited note, removing it when left empty.
+    pub fn commit_note(&mut self) {
+        let Some((symbol, editor)) = self.note_editor.take() else {
+            return;
+        };
+        let note = editor.lines().join(" ").trim().to_owned();
+        let changed = if note.is_empty() {
+            self.config.notes.remove(&symbol).is_some()
+        } else {
+            self.config.notes.insert(symbol, note.clone()) != Some(note)
+        };
+        self.notes_changed |= changed;
+    }
 
-        render_footer(f, app, rects[1]);
+    /// Save the favorites' chart history for the next launch, called on quit
+    /// with `persist_history`.
+    fn save_history(&self) {
+        if !self.config.persist_history.unwrap_or(false) {
+            return;
+        }
+        let Some(path) = history::path() else {
+            return;
+        };
+        let favorites = self
+            .history
+            .iter()
+            .filter(|(symbol, _)| self.favorites.contains(*symbol));
+        if let Err(e) = history::save(&path, favorites) {
+            log::error!("Saving the price history failed: {e}");
+        }
     }
-}
 
-fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
-    let popup_layout = Layout::default()
-        .direction(Direction::Vertical)
-        .constraints([
-            Constraint::Percentage((100 - percent_y) / 2),
-            Constraint::Min(4),
-            Constraint::Percentage((100 - percent_y) / 2),
-        ])
-        .split(r);
+    /// Save the config if a note changed, called on quit.
+    fn save_notes(&self) {
+        if self.notes_changed {
+            if let Err(e) = self.config.save() {
+                log::error!("Saving notes failed: {e}");
+            }
+        }
+    }
 
-    Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage((100 - percent_x) / 2),
-            Constraint::Min(45),
-            Constraint::Percentage((100 - percent_x) / 2),
-        ])
-        .split(popup_layout[1])[1]
-}
+    /// Visible rows that are marked, or `None` when nothing is marked.
+    fn marked_rows(&self) -> Option<Vec<&MarketRow>> {
+        let rows = self
+            .market_data
+            .iter()
+            .filter(|row| self.marked.contains(&row.symbol))
+            .collect::<Vec<_>>();
+        (!rows.is_empty()).then_some(rows)
+    }
 
-fn render_textarea(f: &mut Frame, app: &mut App) {
-    let area = centered_rect(f.size(), 20, 20);
-    f.render_widget(app.textarea.widget(), area);
-}
+    /// Export the marked rows, or every visible row, to a CSV file.
+    pub fn export(&mut self) {
+        let rows = self
+            .marked_rows()
+            .unwrap_or_else(|| self.market_data.iter().collect());
+        let path = export::default_path("csv", self.timezone);
+        self.flash(match export::write_csv(&path, &rows) {
+            Ok(()) => format!("Exported {} rows to {}", rows.len(), path.display()),
+            Err(e) => format!("Export failed: {e}"),
+        });
+    }
 
-fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
-    let header_style = Style::default()
-        .fg(app.colors.header_fg)
-        .bg(app.colors.header_bg);
-    let selected_style = Style::default()
-        .add_modifier(Modifier::REVERSED)
-        .fg(app.colors.selected_style_fg);
+    /// Pick the selected symbol for the side-by-side view: the first pick
+    /// is remembered, a second different one opens the view and picking the
+    /// same one again cancels.
+    fn pick_for_pair(&mut self) {
+        let Some(symbol) = self
+            .state
+            .selected()
+            .and_then(|i| self.market_data.get(i))
+            .map(|row| row.symbol.clone())
+        else {
+            return;
+        };
+        match self.pair_pick.take() {
+            Some(first) if first == symbol => self.flash("Comparison cancelled"),
+            Some(first) => self.side_by_side = Some((first, symbol)),
+            None => {
+                self.flash(format!("Comparing {symbol}, pick another coin with v"));
+                self.pair_pick = Some(symbol);
+            }
+        }
+    }
 
-    let header = ["Symbol", "Price"]
-        .into_iter()
-        .map(Cell::from)
-        .collect::<Row>()
-        .style(header_style)
-        .height(1);
-    let rows = app.market_data.iter().enumerate().map(|(i, data)| {
-        let color = match i % 2 {
-            0 => app.colors.normal_row_color,
-            _ => app.colors.alt_row_color,
-        };
-        let item = [data.symbol.as_str(), &data.price.to_string()];
-        item.into_iter()
-            .map(|content| Cell::from(Text::from(format!("\n{content}\n"))))
-            .collect::<Row>()
-            .style(Style::new().fg(app.colors.row_fg).bg(color))
-            .height(3)
-    });
-    let bar = " █ ";
-    let t = Table::new(
-        rows,
-        [
-            // + 1 is for padding.
-            Constraint::Min(app.longest_item_lens.0 + 1),
-            Constraint::Min(app.longest_item_lens.1 + 1),
-        ],
-    )
-    .header(header)
-    .highlight_style(selected_style)
-    .highlight_symbol(Text::from(vec![
-        "".into(),
-        bar.into(),
-        bar.into(),
-        "".into(),
-    ]))
-    .bg(app.colors.buffer_bg)
-    .highlight_spacing(HighlightSpacing::Always);
-    f.render_stateful_widget(t, area, &mut app.state);
-}
+    /// Base asset of the selected symbol, e.g. `BTC` for `BTCUSDT`.
+    fn selected_base(&self) -> Option<&str> {
+        let row = self
+            .state
+            .selected()
+            .and_then(|i| self.market_data.get(i))?;
+        Some(
+            row.symbol
+                .strip_suffix(self.quote_of(&row.symbol))
+                .unwrap_or(&row.symbol),
+        )
+    }
 
-fn render_scrollbar(f: &mut Frame, app: &mut App, area: Rect) {
-    f.render_stateful_widget(
-        Scrollbar::default()
-            .orientation(ScrollbarOrientation::VerticalRight)
-            .begin_symbol(None)
-            .end_symbol(None),
-        area.inner(&Margin {
-            vertical: 1,
-            horizontal: 1,
-        }),
-        &mut app.scroll_state,
-    );
+    /// Open the stablecoin view for the selected base asset.
+    pub async fn open_stable_view(&mut self) {
+        let Some(base) = self.selected_base().map(str::to_owned) else {
+            return;
+        };
+        let quotes = if self.config.stablecoins.is_empty() {
+            DEFAULT_STABLECOINS.map(str::to_owned).to_vec()
+        } else {
+            self.config.stablecoins.clone()
+        };
+        let prices = if self.quote_asset == ALL_QUOTES {
+            // the last fetch already has every pair
+            let rows = self.held_rows.as_ref().unwrap_or(&self.rows);
+            Ok(coinmarket::base_prices(
+                rows.iter().map(|row| (row.symbol.as_str(), row.price)),
+                &base,
+                &quotes,
+            ))
+        } else {
+            coinmarket::pair_prices(&base, &quotes, self.credentials.as_ref()).await
+        };
+        let (prices, error) = match prices {
+            Ok(prices) => (prices, None),
+            Err(e) => (Vec::new(), Some(e.to_string())),
+        };
+        self.stable_view = Some(StableView {
+            base,
+            prices,
+            error,
+        });
+    }
+
+    /// Open the selected symbol's page in the browser.
+    pub fn open_in_browser(&mut self) {
+        let Some(row) = self.state.selected().and_then(|i| self.market_data.get(i)) else {
+            return;
+        };
+        let quote = self.quote_of(&row.symbol);
+        let base = row.symbol.strip_suffix(quote).unwrap_or(&row.symbol);
+        let template = self
+            .config
+            .url_template
+            .as_deref()
+            .unwrap_or(browser::DEFAULT_URL_TEMPLATE);
+        let url = browser::url(template, base, quote);
+        self.flash(match browser::open(&url) {
+            Ok(()) => format!("Opened {url}"),
+            Err(e) => format!("Unable to open {url}: {e}"),
+        });
+    }
+
+    /// Copy the marked rows, or the selected row, to the clipboard.
+    pub fn copy(&mut self) {
+        let rows = self.marked_rows().unwrap_or_else(|| {
+            self.state
+                .selected()
+                .and_then(|i| self.market_data.get(i))
+                .into_iter()
+                .collect()
+        });
+        if rows.is_empty() {
+            return;
+        }
+        let text = rows
+            .iter()
+            .map(|row| {
+                format!(
+                    "{} {}",
+                    row.symbol,
+                    format_price(row.price, &self.number_format)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.flash(match clipboard::copy(&text) {
+            Ok(()) => format!("Copied {} rows", rows.len()),
+            Err(e) => format!("Copy failed: {e}"),
+        });
+    }
+
+    /// Headers and cell texts of `rows` in the columns shown at the last
+    /// render, leaving out the gutter.
+    fn table_cells(&self, rows: &[&MarketRow]) -> (Vec<&'static str>, Vec<Vec<String>>) {
+        let columns = self
+            .columns
+            .iter()
+            .filter(|column| **column != Column::Gutter)
+            .copied()
+            .collect::<Vec<_>>();
+        let headers = columns
+            .iter()
+            .map(|column| self.column_title(*column))
+            .collect::<Vec<_>>();
+        let cells = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| match column {
+                        Column::Gutter => String::new(),
+                        Column::Symbol => row.symbol.clone(),
+                        Column::Quote => row.quote().unwrap_or("-").to_owned(),
+                        Column::Price => self.price_text(&row.symbol, row.price),
+                        Column::Change => format_change(self.change(row)),
+                        Column::Volume => row.quote_volume.map_or_else(
+                            || "-".to_owned(),
+                            |volume| format_price_fixed(volume, 0, &self.number_format),
+                        ),
+                        Column::Vwap => row.vwap.map_or_else(
+                            || "-".to_owned(),
+                            |vwap| self.format_price(&row.symbol, vwap),
+                        ),
+                        Column::Change7d => format_change(row.change_7d),
+                        Column::Updated => self.updated_text(&row.symbol),
+                    })
+                    .collect()
+            })
+            .collect();
+        (headers, cells)
+    }
+
+    /// The rows on screen at the last render as plain aligned text, printed
+    /// on exit with `--no-altscreen`.
+    fn screen_text(&self) -> String {
+        let start = self.state.offset().min(self.market_data.len());
+        let end = (start + self.visible_rows).min(self.market_data.len());
+        let rows = self.market_data[start..end].iter().collect::<Vec<_>>();
+        let (headers, cells) = self.table_cells(&rows);
+        let columns = self
+            .columns
+            .iter()
+            .filter(|column| **column != Column::Gutter)
+            .collect::<Vec<_>>();
+        let widths = headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                cells
+                    .iter()
+                    .map(|row| row[i].width())
+                    .chain([header.width()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+        let line = |texts: Vec<&str>| {
+            let padded = texts
+                .iter()
+                .zip(&widths)
+                .zip(&columns)
+                .map(|((text, width), column)| {
+                    let padding = " ".repeat(width - text.width());
+                    match column.alignment() {
+                        Alignment::Right => format!("{padding}{text}"),
+                        _ => format!("{text}{padding}"),
+                    }
+                })
+                .collect::<Vec<_>>();
+            format!("{}\n", padded.join("  ").trim_end())
+        };
+        let mut out = line(headers);
+        for row in &cells {
+            out.push_str(&line(row.iter().map(String::as_str).collect()));
+        }
+        out
+    }
+
+    /// Copy the marked rows, or the selected row, as a table of the visible
+    /// columns. Without a terminal to copy through the table is saved to a
+    /// file instead.
+    pub fn copy_table(&mut self) {
+        let rows = self.marked_rows().unwrap_or_else(|| {
+            self.state
+                .selected()
+                .and_then(|i| self.market_data.get(i))
+                .into_iter()
+                .collect()
+        });
+        if rows.is_empty() {
+            return;
+        }
+        let (headers, cells) = self.table_cells(&rows);
+        let format = self.config.copy_format.unwrap_or_default();
+        let text = format.table(&headers, &cells);
+        self.flash(match clipboard::copy(&text) {
+            Ok(()) => format!("Copied {} rows as {format:?}", rows.len()),
+            Err(e) => {
+                let path = export::default_path(format.extension(), self.timezone);
+                match std::fs::write(&path, &text) {
+                    Ok(()) => format!("Copy failed ({e}), saved to {}", path.display()),
+                    Err(write_error) => format!("Copy failed: {e}; {write_error}"),
+                }
+            }
+        });
+    }
+
+    /// Copy every row of the table, not just those on screen, as a JSON
+    /// array of [`MarketRow`]s.
+    pub fn copy_json(&mut self) {
+        if self.market_data.is_empty() {
+            return;
+        }
+        let result = serde_json::to_string_pretty(&self.market_data)
+            .map_err(std::io::Error::from)
+            .and_then(|json| clipboard::copy(&json));
+        self.flash(match result {
+            Ok(()) => format!("Copied {} rows as JSON", self.market_data.len()),
+            Err(e) => format!("Copy failed: {e}"),
+        });
+    }
+
+    /// Fetch fresh market data for the current search in the background,
+    /// so the screen keeps drawing meanwhile; [`App::finish_refresh`] takes
+    /// the result.
+    pub fn refresh(&mut self, kind: RefreshKind) {
+        // there are no statistics to keep before the first load
+        let kind = if self.loaded { kind } else { RefreshKind::Full };
+        self.ticks_since_refresh = 0;
+        self.refresh_delay = rand::thread_rng().gen_range(0..=self.refresh_jitter);
+        self.request_symbol_precision();
+        self.refresh_started = Some(Instant::now());
+        self.refresh_generation += 1;
+        let generation = self.refresh_generation;
+        let tx = self.action_tx.clone();
+        let coin = self.search_coin.clone();
+        let quote = self.quote_asset.clone();
+        let exact = self.config.exact_search.unwrap_or(false);
+        let credentials = self.credentials.clone();
+        self.supervisor.spawn("refresh", move || {
+            let (tx, coin, quote, credentials) =
+                (tx.clone(), coin.clone(), quote.clone(), credentials.clone());
+            async move {
+                let result =
+                    fetch_rows(kind, coin.as_deref(), &quote, exact, credentials.as_ref()).await;
+                let _ = tx.send(Action::Fetched(generation, kind, result.map_err(Arc::new)));
+            }
+        });
+    }
+
+    /// Re-apply the filter to the rows of the refresh numbered `generation`.
+    /// A failed fetch keeps the previous rows and reports the cause.
+    pub fn finish_refresh(&mut self, generation: u64, kind: RefreshKind, result: FetchResult) {
+        if generation != self.refresh_generation {
+            return;
+        }
+        let Some(started) = self.refresh_started.take() else {
+            return;
+        };
+        if self.credentials.is_some() {
+            if let Some(e) = result
+                .as_ref()
+                .err()
+                .and_then(|e| e.downcast_ref::<InvalidCredentials>())
+            {
+                log::warn!("{e}, continuing with anonymous access");
+                self.credentials = None;
+                self.refresh(kind);
+                return;
+            }
+        }
+        if let Some(metrics) = &mut self.metrics {
+            metrics.record_fetch(started);
+        }
+        match result {
+            Ok((mut rows, stats)) => {
+                self.last_update = Instant::now();
+                self.data_stale = false;
+                match stats {
+                    StatsFetch::Fresh => {
+                        self.ticks_since_stats = 0;
+                        self.stats_stale = false;
+                        self.stats_interval = self.refresh_interval;
+                    }
+                    // keep the last statistics until the next full refresh
+                    StatsFetch::NotRequested | StatsFetch::Failed(_) => {
+                        if let StatsFetch::Failed(e) = &stats {
+                            log::warn!("{e}, keeping the previous 24h stats");
+                            self.stats_stale = true;
+                            if e.downcast_ref::<RateLimited>().is_some() {
+                                // back off until Binance stops throttling
+                                self.stats_interval = self
+                                    .stats_interval
+                                    .saturating_mul(2)
+                                    .min(secs_to_ticks(MAX_STATS_INTERVAL));
+                            }
+                        }
+                        let stats = self
+                            .held_rows
+                            .as_ref()
+                            .unwrap_or(&self.rows)
+                            .iter()
+                            .map(|row| (row.symbol.as_str(), row))
+                            .collect::<HashMap<_, _>>();
+                        for row in &mut rows {
+                            if let Some(old) = stats.get(row.symbol.as_str()) {
+                                row.change_pct = old.change_pct;
+                                row.vwap = old.vwap;
+                                row.quote_volume = old.quote_volume;
+                                row.high = old.high;
+                                row.low = old.low;
+                            }
+                        }
+                    }
+                }
+                self.check_moves(&rows);
+                self.check_alerts(&rows);
+                self.record_history(&rows);
+                if self.paused || self.view_frozen() {
+                    self.held_rows = Some(rows);
+                } else {
+                    self.set_rows(rows);
+                }
+            }
+            // not a failure: the search simply has nothing for this quote.
+            // A pause keeps the shown rows until a refresh after it.
+            Err(e) if e.downcast_ref::<NoPairs>().is_some() => {
+                if self.paused || self.view_frozen() {
+                    return;
+                }
+                self.rows.clear();
+                self.empty_reason = Some(e.to_string());
+                self.loaded = true;
+                self.startup_error = None;
+                self.apply_filter();
+            }
+            Err(e) => {
+                log::error!("Refresh failed: {e}");
+                if self.loaded {
+                    self.flash(format!("Refresh failed: {e}"));
+                } else {
+                    self.startup_error = Some(e.to_string());
+                }
+            }
+        }
+    }
+
+    /// Append the fetched prices to the chart history and stamp the symbols
+    /// whose price changed since the previous fetch.
+    fn record_history(&mut self, rows: &[MarketRow]) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or_default();
+        let local_now = Local::now();
+        let change_flash = self.change_flash;
+        let history_len = self.history_len;
+        self.price_flashes
+            .retain(|_, (_, at)| at.elapsed() < change_flash);
+        for row in rows {
+            if self.favorites.contains(&row.symbol) {
+                self.digest_baseline
+                    .entry(row.symbol.clone())
+                    .or_insert(row.price);
+            }
+            let samples = self.history.entry(row.symbol.clone()).or_default();
+            if samples.back().is_none_or(|(_, price)| *price != row.price) {
+                self.updated_at.insert(row.symbol.clone(), local_now);
+            }
+            // the first fetch has nothing to compare with
+            if let Some((_, previous)) = samples.back().filter(|(_, price)| *price != row.price) {
+                self.price_flashes
+                    .insert(row.symbol.clone(), (row.price > *previous, Instant::now()));
+            }
+            let excess = (samples.len() + 1).saturating_sub(history_len);
+            samples.drain(..excess);
+            samples.push_back((now, row.price));
+        }
+    }
+
+    /// Put the search, filter, sort, notation, precision and optional
+    /// columns back to how they start. Favorites and the config file are
+    /// left alone.
+    fn reset_view(&mut self) {
+        self.search_coin = None;
+        self.filter = None;
+        self.sort = SortState::default();
+        self.notation = Notation::Decimal;
+        self.inverse = false;
+        self.precision = self.config.precision.map(|p| p.min(MAX_PRECISION));
+        self.show_7d = false;
+        self.timeframe = Timeframe::default();
+        self.show_chart = false;
+        self.show_updated = false;
+        self.only_moved = false;
+        self.view = ViewMode::All;
+        self.view_positions.clear();
+        self.quick_filter = None;
+        self.pair_pick = None;
+        // the default search asks for different rows, as a new search does
+        self.paused = false;
+        self.held_rows = None;
+        self.known_symbols = None;
+        self.textarea.select_all();
+        self.textarea.cut();
+    }
+
+    /// Fall back from a favorites startup view without favorites, which
+    /// would only be an empty table.
+    fn check_startup_view(&mut self) {
+        if self.view == ViewMode::Favorites && self.favorites.is_empty() {
+            self.view = ViewMode::All;
+            self.flash("No favorites yet, showing all pairs; add some with f");
+        }
+    }
+
+    /// Show only the quick filter `index`, or every row again when it is
+    /// already shown.
+    fn toggle_quick_filter(&mut self, index: usize) {
+        let Some(quick) = self.quick_filters.get(index) else {
+            self.flash(format!(
+                "No quick filter {}, add quick_filters to the config",
+                index + 1
+            ));
+            return;
+        };
+        if self.quick_filter == Some(index) {
+            self.quick_filter = None;
+        } else {
+            self.flash(format!("Showing {}", quick.name));
+            self.quick_filter = Some(index);
+        }
+        let position = self.view_position();
+        self.apply_filter();
+        self.restore_view_position(position);
+    }
+
+    /// Show the next set of rows: all, favorites or top movers.
+    fn cycle_view(&mut self) {
+        self.view_positions.insert(self.view, self.view_position());
+        self.view = self.view.next();
+        self.apply_filter();
+        // a view not visited yet starts at the top
+        let position = self
+            .view_positions
+            .get(&self.view)
+            .cloned()
+            .unwrap_or_default();
+        self.restore_view_position(position);
+    }
+
+    /// Queue the notes of the releases since the last run and remember the
+    /// running version for next time.
+    fn check_version(&mut self) {
+        // the version of a config that failed to load is unknown, and saving
+        // it would replace the file with defaults
+        if self.config.load_failed
+            || self.config.last_version.as_deref() == Some(whats_new::VERSION)
+        {
+            return;
+        }
+        self.whats_new = whats_new::since(self.config.last_version.as_deref());
+        self.config.last_version = Some(whats_new::VERSION.to_owned());
+        if let Err(e) = self.config.save() {
+            log::warn!("Saving the config version failed: {e}");
+        }
+    }
+
+    /// Ring the bell and flash the favorites that moved more than
+    /// [`App::move_alert`] percent since the last fetch.
+    fn check_moves(&mut self, rows: &[MarketRow]) {
+        let Some(threshold) = self.move_alert else {
+            return;
+        };
+        let mut moved = Vec::new();
+        for row in rows {
+            if !self.favorites.contains(&row.symbol) {
+                continue;
+            }
+            let Some(&(_, previous)) = self.history.get(&row.symbol).and_then(VecDeque::back)
+            else {
+                continue;
+            };
+            if previous <= 0.0 {
+                continue;
+            }
+            let change = (row.price / previous - 1.0) * 100.0;
+            if change.abs() < threshold {
+                self.moving.remove(&row.symbol);
+            } else if self.moving.insert(row.symbol.clone()) {
+                moved.push(format!("{} {}", row.symbol, format_change(Some(change))));
+            }
+        }
+        if !moved.is_empty() {
+            ring_bell();
+            self.flash(format!("Moved: {}", moved.join(", ")));
+        }
+    }
+
+    /// Ring the bell and flash the alerts whose target `rows` reached,
+    /// marking them triggered so they ring only once.
+    fn check_alerts(&mut self, rows: &[MarketRow]) {
+        let prices = rows
+            .iter()
+            .map(|row| (row.symbol.as_str(), row.price))
+            .collect::<HashMap<_, _>>();
+        let mut reached = Vec::new();
+        for alert in self
+            .config
+            .alerts
+            .iter_mut()
+            .filter(|alert| !alert.triggered)
+        {
+            let Some(&price) = prices.get(alert.symbol.as_str()) else {
+                continue;
+            };
+            if alert.reached(price) {
+                alert.triggered = true;
+                reached.push((alert.symbol.clone(), alert.direction, alert.target));
+            }
+        }
+        if reached.is_empty() {
+            return;
+        }
+        ring_bell();
+        let reached = reached
+            .iter()
+            .map(|(symbol, direction, target)| {
+                format!(
+                    "{symbol} {} {}",
+                    direction.label(),
+                    self.format_price(symbol, *target)
+                )
+            })
+            .collect::<Vec<_>>();
+        self.flash(format!("Alert: {}", reached.join(", ")));
+        self.save_alerts();
+    }
+
+    fn save_alerts(&mut self) {
+        if let Err(e) = self.config.save() {
+            self.flash(format!("Saving alerts failed: {e}"));
+        }
+    }
+
+    /// Open the alerts screen with the first alert selected.
+    pub fn open_alerts(&mut self) {
+        let selected = (!self.config.alerts.is_empty()).then_some(0);
+        self.alerts_view = Some(AlertsView {
+            state: TableState::default().with_selected(selected),
+            ..AlertsView::default()
+        });
+    }
+
+    /// Move the alerts selection by `delta`, wrapping around.
+    pub fn move_in_alerts(&mut self, delta: isize) {
+        let count = self.config.alerts.len() as isize;
+        let Some(view) = &mut self.alerts_view else {
+            return;
+        };
+        if count > 0 {
+            let i = view.state.selected().unwrap_or(0) as isize;
+            view.state
+                .select(Some((i + delta).rem_euclid(count) as usize));
+        }
+    }
+
+    /// Start typing a new alert on the symbol selected in the table.
+    pub fn new_alert(&mut self) {
+        let symbol = self
+            .state
+            .selected()
+            .and_then(|i| self.market_data.get(i))
+            .map(|row| row.symbol.clone());
+        let colors = &self.colors;
+        let Some(view) = &mut self.alerts_view else {
+            return;
+        };
+        let Some(symbol) = symbol else {
+            view.error = Some("Select a coin in the table first".to_owned());
+            return;
+        };
+        view.editor = Some(AlertEdit {
+            index: None,
+            input: alert_editor(&format!("New alert on {symbol}"), String::new(), colors),
+            symbol,
+        });
+        view.error = None;
+    }
+
+    /// Start editing the target of the selected alert.
+    pub fn edit_alert(&mut self) {
+        let colors = &self.colors;
+        let Some(view) = &mut self.alerts_view else {
+            return;
+        };
+        let Some((index, alert)) = view
+            .state
+            .selected()
+            .and_then(|i| Some((i, self.config.alerts.get(i)?)))
+        else {
+            return;
+        };
+        let side = match alert.direction {
+            config::AlertDirection::Above => '>',
+            config::AlertDirection::Below => '<',
+        };
+        view.editor = Some(AlertEdit {
+            index: Some(index),
+            input: alert_editor(
+                &format!("Alert on {}", alert.symbol),
+                format!("{side}{}", alert.target),
+                colors,
+            ),
+            symbol: alert.symbol.clone(),
+        });
+        view.error = None;
+    }
+
+    /// Save the alert being typed, keeping the editor open on errors. An
+    /// edited alert is armed again.
+    pub fn commit_alert(&mut self) {
+        let Some(edit) = self
+            .alerts_view
+            .as_mut()
+            .and_then(|view| view.editor.take())
+        else {
+            return;
+        };
+        let price = self
+            .rows
+            .iter()
+            .find(|row| row.symbol == edit.symbol)
+            .map(|row| row.price);
+        match PriceAlert::parse_target(&edit.input.lines().join(""), price) {
+            Ok((target, direction)) => {
+                let alert = PriceAlert {
+                    symbol: edit.symbol,
+                    target,
+                    direction,
+                    triggered: false,
+                };
+                let index = match edit.index {
+                    Some(i) if i < self.config.alerts.len() => {
+                        self.config.alerts[i] = alert;
+                        i
+                    }
+                    _ => {
+                        self.config.alerts.push(alert);
+                        self.config.alerts.len() - 1
+                    }
+                };
+                if let Some(view) = &mut self.alerts_view {
+                    view.state.select(Some(index));
+                    view.error = None;
+                }
+                self.save_alerts();
+            }
+            Err(e) => {
+                if let Some(view) = &mut self.alerts_view {
+                    view.error = Some(e);
+                    view.editor = Some(edit);
+                }
+            }
+        }
+    }
+
+    /// Delete the selected alert.
+    pub fn delete_alert(&mut self) {
+        let Some(i) = self
+            .alerts_view
+            .as_ref()
+            .and_then(|view| view.state.selected())
+            .filter(|i| *i < self.config.alerts.len())
+        else {
+            return;
+        };
+        self.config.alerts.remove(i);
+        let count = self.config.alerts.len();
+        if let Some(view) = &mut self.alerts_view {
+            view.state.select((count > 0).then(|| i.min(count - 1)));
+        }
+        self.save_alerts();
+    }
+
+    /// Whether the shown rows were updated at different times; otherwise
+    /// the update column tells nothing the footer does not.
+    fn updates_differ(&self) -> bool {
+        let mut times = self
+            .market_data
+            .iter()
+            .filter_map(|row| self.updated_at.get(&row.symbol));
+        let first = times.next();
+        times.any(|time| Some(time) != first)
+    }
+
+    /// The time `symbol`'s price last changed, as `HH:MM:SS`.
+    fn updated_text(&self, symbol: &str) -> String {
+        self.updated_at.get(symbol).map_or_else(
+            || "-".to_owned(),
+            |time| self.timezone.format_time(time, "%H:%M:%S"),
+        )
+    }
+
+    pub fn toggle_updated(&mut self) {
+        self.show_updated = !self.show_updated;
+        if self.show_updated && !self.updates_differ() {
+            self.flash("Every row was updated at the same time, the time column is hidden");
+        }
+    }
+
+    /// Show freshly fetched `rows`, with the cached 7d change filled in.
+    fn set_rows(&mut self, mut rows: Vec<MarketRow>) {
+        for row in &mut rows {
+            if let Some(close) = self.week_ago.peek(&row.symbol) {
+                row.set_week_ago_close(*close);
+            }
+        }
+        self.rows = rows;
+        // held rows are the latest fetch too, each one replacing the last
+        self.shown_update = self.last_update;
+        self.empty_reason = None;
+        self.detect_listing_changes();
+        self.loaded = true;
+        self.startup_error = None;
+        self.apply_filter();
+    }
+
+    /// Pause, or resume and show the rows fetched meanwhile.
+    pub fn toggle_pause(&mut self) {
+        if self.paused {
+            self.resume();
+        } else {
+            self.paused = true;
+        }
+    }
+
+    /// Whether a popup or the search box is open over the table, so a
+    /// refresh would reorder rows under it. The first rows are never held.
+    fn view_frozen(&self) -> bool {
+        self.hold_behind_popups
+            && self.loaded
+            && (self.focus_textarea
+                || self.settings.is_some()
+                || self.stable_view.is_some()
+                || self.show_log
+                || self.show_digest
+                || self.note_editor.is_some()
+                || self.palette.is_some()
+                || self.alerts_view.is_some()
+                || !self.whats_new.is_empty())
+    }
+
+    /// Show the rows held back while the view was frozen, once it is not.
+    fn release_held_rows(&mut self) {
+        if !self.paused && !self.view_frozen() {
+            if let Some(rows) = self.held_rows.take() {
+                self.set_rows(rows);
+            }
+        }
+    }
+
+    /// Stop holding rows back, showing the latest held batch if any.
+    pub fn resume(&mut self) {
+        self.paused = false;
+        if let Some(rows) = self.held_rows.take() {
+            self.set_rows(rows);
+        }
+    }
+
+    /// Ticks after the last refresh at which auto-refresh fetches again.
+    fn refresh_due(&self) -> u32 {
+        self.refresh_interval.saturating_add(self.refresh_delay)
+    }
+
+    /// Each favorite's baseline and latest price with the move between them,
+    /// largest moves first.
+    fn digest(&self) -> Vec<(&str, f64, f64, f64)> {
+        let mut moves = self
+            .digest_baseline
+            .iter()
+            .filter(|(symbol, _)| self.favorites.contains(*symbol))
+            .filter_map(|(symbol, baseline)| {
+                let (_, price) = self.history.get(symbol)?.back()?;
+                let change = (*baseline > 0.0).then(|| (price / baseline - 1.0) * 100.0)?;
+                Some((symbol.as_str(), *baseline, *price, change))
+            })
+            .collect::<Vec<_>>();
+        moves.sort_by(|a, b| b.3.abs().total_cmp(&a.3.abs()).then(a.0.cmp(b.0)));
+        moves
+    }
+
+    /// Close the digest, measuring the next one from the prices now.
+    fn dismiss_digest(&mut self) {
+        self.show_digest = false;
+        self.digest_baseline = self
+            .favorites
+            .iter()
+            .filter_map(|symbol| {
+                let (_, price) = self.history.get(symbol)?.back()?;
+                Some((symbol.clone(), *price))
+            })
+            .collect();
+        self.digest_since = Local::now();
+    }
+
+    /// Footer text describing data age and when the next refresh happens.
+    pub fn refresh_status(&self) -> String {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let secs = |ticks: u32| (f64::from(ticks) / TICK_RATE).ceil() as u32;
+        let mut updated = format!("updated {}s ago", self.shown_update.elapsed().as_secs());
+        if self.ticks_since_stats > self.ticks_since_refresh {
+            updated.push_str(&format!(
+                " (24h stats {}s ago)",
+                secs(self.ticks_since_stats)
+            ));
+        }
+        if self.stats_stale {
+            updated.push_str(" · 24h stats unavailable");
+        }
+        if self.auto_refresh {
+            let remaining = self.refresh_due().saturating_sub(self.ticks_since_refresh);
+            format!("{updated} · next refresh in {}s", secs(remaining))
+        } else {
+            format!("{updated} · manual")
+        }
+    }
+
+    /// Compare the fetched symbols with the previous fetch and raise a
+    /// flash message for additions and removals.
+    fn detect_listing_changes(&mut self) {
+        let symbols = self
+            .rows
+            .iter()
+            .map(|row| row.symbol.clone())
+            .collect::<HashSet<_>>();
+        let Some(previous) = self.known_symbols.take() else {
+            self.known_symbols = Some(symbols);
+            return;
+        };
+        let mut listed = symbols.difference(&previous).cloned().collect::<Vec<_>>();
+        let mut delisted = previous.difference(&symbols).cloned().collect::<Vec<_>>();
+        listed.sort();
+        delisted.sort();
+        self.known_symbols = Some(symbols);
+
+        let mut parts = Vec::new();
+        if !listed.is_empty() {
+            parts.push(format!("New pair listed: {}", listed.join(", ")));
+        }
+        if !delisted.is_empty() {
+            parts.push(format!("Delisted: {}", delisted.join(", ")));
+        }
+        if !parts.is_empty() {
+            self.flash(parts.join(" · "));
+        }
+    }
+
+    /// Move to the next sort column, starting in that column's natural direction.
+    pub fn cycle_sort(&mut self) {
+        let mode = self.sort.mode.next();
+        self.sort = SortState {
+            mode,
+            descending: mode.default_descending(),
+        };
+        self.apply_filter();
+    }
+
+    /// Switch to sorting by 24h quote volume, most traded first, or back to
+    /// the sort before it.
+    pub fn toggle_volume_sort(&mut self) {
+        if self.sort.mode == SortMode::Volume {
+            self.sort = self.sort_before_volume.take().unwrap_or_default();
+        } else {
+            self.sort_before_volume = Some(self.sort);
+            self.sort = SortState {
+                mode: SortMode::Volume,
+                descending: true,
+            };
+        }
+        self.apply_filter();
+    }
+
+    /// Flip the direction of the current sort column, keeping the selection.
+    pub fn toggle_sort_direction(&mut self) {
+        if self.sort.mode == SortMode::None {
+            self.flash("Not sorted, press S to pick a sort column");
+            return;
+        }
+        let position = self.view_position();
+        self.sort.descending = !self.sort.descending;
+        self.sort_rows();
+        self.restore_view_position(position);
+        self.publish();
+    }
+
+    /// Hand the shown rows to the `--serve` server, if running.
+    fn publish(&self) {
+        if let Some(served) = &self.served {
+            served.set(&self.market_data);
+        }
+    }
+
+    /// Sort the shown rows by the current sort, measuring changes over the
+    /// selected window.
+    fn sort_rows(&mut self) {
+        if self.sort.mode == SortMode::Change && self.timeframe != Timeframe::Day {
+            let mut rows = std::mem::take(&mut self.market_data);
+            sort_by_optional(&mut rows, self.sort.descending, |row| self.change(row));
+            self.market_data = rows;
+        } else {
+            sort_market_data(&mut self.market_data, self.sort.mode, self.sort.descending);
+        }
+    }
+
+    /// Price change of `row` in percent over the selected window; `None`
+    /// until the start of a short window is fetched.
+    pub fn change(&self, row: &MarketRow) -> Option<f64> {
+        if self.timeframe == Timeframe::Day {
+            return row.change_pct;
+        }
+        let open = self.window_opens.get(&self.timeframe)?.peek(&row.symbol)?;
+        (*open > 0.0).then(|| (row.price - open) / open * 100.0)
+    }
+
+    /// Highlight of a price that changed within [`App::change_flash`].
+    fn price_flash_style(&self, symbol: &str) -> Style {
+        match self.price_flashes.get(symbol) {
+            Some((up, at)) if at.elapsed() < self.change_flash => {
+                if *up {
+                    self.colors.flash_up_style
+                } else {
+                    self.colors.flash_down_style
+                }
+            }
+            _ => Style::new(),
+        }
+    }
+
+    /// Title of `column` in the header, naming what it currently shows.
+    fn column_title(&self, column: Column) -> &'static str {
+        match column {
+            Column::Price if self.inverse => "1/Price",
+            Column::Change => match self.timeframe {
+                Timeframe::Hour => "1h %",
+                Timeframe::FourHours => "4h %",
+                Timeframe::Day => "24h %",
+            },
+            column => column.title(),
+        }
+    }
+
+    /// Header of `column` as shown, with the sort arrow when sorted by it.
+    fn header_title(&self, column: Column) -> String {
+        let mut title = self.column_title(column).to_owned();
+        let mode = column.sort_mode();
+        if mode != SortMode::None && mode == self.sort.mode {
+            title.push_str(if self.sort.descending { " ▼" } else { " ▲" });
+        }
+        title
+    }
+
+    /// Switch the change column to the next window.
+    pub fn cycle_timeframe(&mut self) {
+        self.timeframe = self.timeframe.next();
+        self.request_window_opens();
+        self.sort_rows();
+        self.update_lens();
+    }
+
+    /// Fetch the start of the selected window in the background for the
+    /// rows on screen, then for the other shown rows while sorted by the
+    /// change, skipping symbols that are fresh in the cache. At most
+    /// [`MAX_WINDOW_OPENS_IN_FLIGHT`] are fetched at once. The 24h window
+    /// needs no fetch.
+    pub fn request_window_opens(&mut self) {
+        let timeframe = self.timeframe;
+        if timeframe == Timeframe::Day
+            || self
+                .window_opens_throttled_until
+                .is_some_and(|until| Instant::now() < until)
+        {
+            return;
+        }
+        let on_screen = self
+            .market_data
+            .iter()
+            .skip(self.state.offset())
+            .take(self.visible_rows.max(1));
+        let rest = self
+            .market_data
+            .iter()
+            .filter(|_| self.sort.mode == SortMode::Change);
+        let cached = self.window_opens.get(&timeframe);
+        let mut wanted = Vec::new();
+        for row in on_screen.chain(rest) {
+            if self.window_opens_pending.len() + wanted.len() >= MAX_WINDOW_OPENS_IN_FLIGHT {
+                break;
+            }
+            let key = (timeframe, row.symbol.clone());
+            if !self.window_opens_pending.contains(&key)
+                && !wanted.contains(&key)
+                && cached.is_none_or(|cached| cached.is_due(&key.1))
+            {
+                wanted.push(key);
+            }
+        }
+        for key in wanted {
+            self.window_opens_pending.insert(key.clone());
+            let tx = self.action_tx.clone();
+            let credentials = self.credentials.clone();
+            let symbol = key.1;
+            self.supervisor.spawn("window open", move || {
+                let (symbol, tx, credentials) = (symbol.clone(), tx.clone(), credentials.clone());
+                async move {
+                    let open =
+                        coinmarket::window_open(&symbol, timeframe, credentials.as_ref()).await;
+                    let action = match open {
+                        Err(e) if e.downcast_ref::<RateLimited>().is_some() => {
+                            log::warn!("{e}, pausing window open lookups");
+                            Action::WindowOpenThrottled(timeframe, symbol)
+                        }
+                        open => {
+                            if let Err(e) = &open {
+                                log::warn!("{e}");
+                            }
+                            Action::WindowOpen(timeframe, symbol, open.ok())
+                        }
+                    };
+                    let _ = tx.send(action);
+                }
+            });
+        }
+    }
+
+    /// Back off from window opens after Binance throttled the one for
+    /// `symbol`, leaving it uncached so it is fetched again afterwards.
+    pub fn window_open_throttled(&mut self, timeframe: Timeframe, symbol: String) {
+        self.window_opens_pending.remove(&(timeframe, symbol));
+        self.window_opens_throttled_until = Some(Instant::now() + WINDOW_OPEN_BACKOFF);
+    }
+
+    /// Store a background window open; the rows showing it pick it up, and
+    /// move to their place when sorted by the change.
+    pub fn set_window_open(&mut self, timeframe: Timeframe, symbol: String, open: Option<f64>) {
+        self.window_opens_pending
+            .remove(&(timeframe, symbol.clone()));
+        // failed lookups are cached too so they are not retried every tick
+        self.window_opens
+            .entry(timeframe)
+            .or_insert_with(|| TtlCache::new(timeframe.refresh_after(), SYMBOL_CACHE_CAPACITY))
+            .insert(symbol, open.unwrap_or(0.0));
+        self.update_lens();
+        if timeframe == self.timeframe && self.sort.mode == SortMode::Change {
+            let position = self.view_position();
+            self.sort_rows();
+            self.restore_view_position(position);
+            self.publish();
+        }
+    }
+
+    /// Fetch the week-ago close in the background for the rows on screen and
+    /// the favorites, skipping symbols that are fresh in the cache.
+    pub fn request_week_ago(&mut self) {
+        let offset = self.state.offset();
+        let visible = self
+            .market_data
+            .iter()
+            .skip(offset)
+            .take(self.visible_rows.max(1))
+            .map(|row| &row.symbol);
+        let wanted = visible
+            .chain(self.favorites.iter())
+            .filter(|symbol| {
+                !self.week_ago_pending.contains(*symbol) && self.week_ago.is_due(symbol.as_str())
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+        for symbol in wanted {
+            self.week_ago_pending.insert(symbol.clone());
+            let tx = self.action_tx.clone();
+            let credentials = self.credentials.clone();
+            self.supervisor.spawn("7d close", move || {
+                let (symbol, tx, credentials) = (symbol.clone(), tx.clone(), credentials.clone());
+                async move {
+                    let close = coinmarket::week_ago_close(&symbol, credentials.as_ref()).await;
+                    if let Err(e) = &close {
+                        log::warn!("{e}");
+                    }
+                    let _ = tx.send(Action::WeekAgoClose(symbol, close.ok()));
+                }
+            });
+        }
+    }
+
+    /// Fetch tick sizes in the background unless they were requested within
+    /// [`SYMBOL_PRECISION_TTL`].
+    pub fn request_symbol_precision(&mut self) {
+        if self
+            .symbol_precision_requested
+            .is_some_and(|requested| requested.elapsed() < SYMBOL_PRECISION_TTL)
+        {
+            return;
+        }
+        self.symbol_precision_requested = Some(Instant::now());
+        let tx = self.action_tx.clone();
+        let credentials = self.credentials.clone();
+        self.supervisor.spawn("tick sizes", move || {
+            let (tx, credentials) = (tx.clone(), credentials.clone());
+            async move {
+                let precision = coinmarket::price_precisions(credentials.as_ref()).await;
+                if let Err(e) = &precision {
+                    log::warn!("{e}, using the quote asset precision");
+                }
+                let _ = tx.send(Action::SymbolPrecision(precision.ok()));
+            }
+        });
+    }
+
+    /// Measure the clock skew against Binance in the background. A failed
+    /// check is only logged.
+    pub fn check_clock(&self) {
+        let tx = self.action_tx.clone();
+        self.supervisor.spawn("clock check", move || {
+            let tx = tx.clone();
+            async move {
+                match coinmarket::server_time_offset().await {
+                    Ok(offset) => {
+                        let _ = tx.send(Action::ClockOffset(offset));
+                    }
+                    Err(e) => log::warn!("{e}, skipping the clock check"),
+                }
+            }
+        });
+    }
+
+    /// Store the measured clock skew and warn when it is large.
+    pub fn set_clock_offset(&mut self, offset: i64) {
+        self.clock_offset = Some(offset);
+        log::info!("Binance server clock is {offset}ms ahead of the local clock");
+        if offset.abs() > CLOCK_SKEW_WARNING {
+            #[allow(clippy::cast_precision_loss)]
+            let secs = offset.abs() as f64 / 1000.0;
+            let direction = if offset > 0 { "behind" } else { "ahead of" };
+            self.flash(format!(
+                "Your clock is {secs:.1}s {direction} Binance, sync it to avoid rejected requests"
+            ));
+        }
+    }
+
+    /// Store background tick sizes; a failed fetch keeps the previous ones
+    /// and is retried at the next refresh.
+    pub fn set_symbol_precision(&mut self, precision: Option<HashMap<String, usize>>) {
+        match precision {
+            Some(precision) => {
+                self.symbol_precision = precision;
+                self.update_lens();
+            }
+            None => self.symbol_precision_requested = None,
+        }
+    }
+
+    /// Store a background week-ago close and update the rows showing it.
+    pub fn set_week_ago(&mut self, symbol: String, close: Option<f64>) {
+        self.week_ago_pending.remove(&symbol);
+        // failed lookups are cached too so they are not retried every tick
+        let close = close.unwrap_or(0.0);
+        for row in self
+            .rows
+            .iter_mut()
+            .chain(self.market_data.iter_mut())
+            .filter(|row| row.symbol == symbol)
+        {
+            row.set_week_ago_close(close);
+        }
+        self.update_lens();
+        self.publish();
+        self.week_ago.insert(symbol, close);
+    }
+
+    /// The selected symbol and the viewport offset, see
+    /// [`App::restore_view_position`].
+    pub fn view_position(&self) -> (Option<String>, usize) {
+        let selected = self.state.selected().and_then(|i| self.market_data.get(i));
+        (selected.map(|row| row.symbol.clone()), self.state.offset())
+    }
+
+    /// Reselect `symbol` and scroll back to `offset` after the rows changed,
+    /// clamping both to the new length. Keeps the first row selected when
+    /// the symbol is gone.
+    pub fn restore_view_position(&mut self, (symbol, offset): (Option<String>, usize)) {
+        let len = self.market_data.len();
+        if len == 0 {
+            return;
+        }
+        let selected = symbol
+            .and_then(|symbol| self.market_data.iter().position(|row| row.symbol == symbol))
+            .unwrap_or(0);
+        let offset = offset.min(len.saturating_sub(self.visible_rows.max(1)));
+        self.state = self
+            .state
+            .clone()
+            .with_selected(selected)
+            .with_offset(offset);
+    }
+
+    /// Current value of `field` as shown on the settings screen.
+    pub fn setting_value(&self, field: Field) -> String {
+        match field {
+            Field::QuoteAsset => self.quote_asset.clone(),
+            Field::RefreshInterval => match self.config.refresh_interval {
+                Some(secs) => secs.to_string(),
+                None => format!("{DEFAULT_REFRESH_INTERVAL} (default)"),
+            },
+            Field::Precision => match self.precision {
+                Some(precision) => precision.to_string(),
+                None if !self.symbol_precision.is_empty() => "per symbol tick size".to_owned(),
+                None => match self.quote_precision.get(&self.quote_asset) {
+                    Some(precision) => format!("{precision} ({} default)", self.quote_asset),
+                    None => "full".to_owned(),
+                },
+            },
+            Field::Theme => PALETTE_NAMES[self.color_index].to_owned(),
+        }
+    }
+
+    /// List the pairs of `quote` from the next refresh on, which is sent
+    /// right away.
+    fn switch_quote(&mut self, quote: String) {
+        self.quote_asset = quote;
+        // the quote's default precision may differ
+        self.update_lens();
+        // other pairs are not listing changes
+        self.known_symbols = None;
+        // rows for the old quote are of no use now
+        self.paused = false;
+        self.held_rows = None;
+        let _ = self.action_tx.send(Action::Refresh);
+    }
+
+    /// Index of the tab of the listed quote asset, if it has one.
+    fn quote_tab(&self) -> Option<usize> {
+        self.quote_tabs
+            .iter()
+            .position(|quote| *quote == self.quote_asset)
+    }
+
+    /// The tab `delta` tabs from the active one, wrapping around; the
+    /// first or last when no tab is active.
+    fn quote_tab_by(&self, delta: isize) -> usize {
+        let count = self.quote_tabs.len() as isize;
+        match self.quote_tab() {
+            Some(i) => (i as isize + delta).rem_euclid(count) as usize,
+            None if delta > 0 => 0,
+            None => self.quote_tabs.len().saturating_sub(1),
+        }
+    }
+
+    /// Whether a popup or another view covers the table and takes the input.
+    fn overlay_open(&self) -> bool {
+        !self.whats_new.is_empty()
+            || self.side_by_side.is_some()
+            || self.stable_view.is_some()
+            || self.alerts_view.is_some()
+            || self.show_digest
+            || self.show_log
+            || self.settings.is_some()
+            || self.palette.is_some()
+            || self.note_editor.is_some()
+    }
+
+    /// The tab drawn at `column` and `row` of the screen, if any.
+    fn quote_tab_at(&self, column: u16, row: u16) -> Option<usize> {
+        if self.tabs_area.is_empty() || row != self.tabs_area.y {
+            return None;
+        }
+        // each title is padded by a space on both sides, then a divider follows
+        let mut x = self.tabs_area.x;
+        for (i, quote) in self.quote_tabs.iter().enumerate() {
+            let width = u16::try_from(quote.width()).unwrap_or(u16::MAX) + 2;
+            if (x..x.saturating_add(width)).contains(&column) {
+                return Some(i);
+            }
+            x = x.saturating_add(width + 1);
+        }
+        None
+    }
+
+    /// Make tab `i` active, listing the pairs of its quote asset.
+    pub fn select_quote_tab(&mut self, i: usize) {
+        if let Some(quote) = self.quote_tabs.get(i) {
+            if *quote != self.quote_asset {
+                self.switch_quote(quote.clone());
+            }
+        }
+    }
+
+    /// Apply an edited `field` immediately and record it in the config.
+    pub fn apply_setting(&mut self, field: Field, input: &str) -> Result<(), String> {
+        let input = input.trim();
+        match field {
+            Field::QuoteAsset => {
+                if input != ALL_QUOTES
+                    && (input.is_empty() || !input.chars().all(|c| c.is_ascii_alphanumeric()))
+                {
+                    return Err(format!("{input:?} is not an asset like USDT, or * for all"));
+                }
+                self.config.quote_asset = Some(input.to_uppercase());
+                self.switch_quote(input.to_uppercase());
+            }
+            Field::RefreshInterval => {
+                let secs = input
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .ok_or_else(|| format!("{input:?} is not a number of seconds"))?;
+                self.config.refresh_interval = Some(secs);
+                self.refresh_interval = secs_to_ticks(secs);
+                self.stats_interval = self.refresh_interval;
+                // a configured interval means auto-refresh, as at startup
+                self.auto_refresh = true;
+                self.ticks_since_refresh = 0;
+            }
+            Field::Precision => {
+                self.precision = match input {
+                    "" | "default" => None,
+                    _ => Some(
+                        input
+                            .parse::<usize>()
+                            .ok()
+                            .filter(|p| *p <= MAX_PRECISION)
+                            .ok_or_else(|| {
+                                format!("{input:?} is not 0 to {MAX_PRECISION}, or empty for the quote default")
+                            })?,
+                    ),
+                };
+                self.config.precision = self.precision;
+                self.update_lens();
+            }
+            Field::Theme => {
+                let index = PALETTE_NAMES
+                    .iter()
+                    .position(|name| name.eq_ignore_ascii_case(input))
+                    .ok_or_else(|| format!("themes are {}", PALETTE_NAMES.join(", ")))?;
+                self.color_index = index;
+                self.config.theme = Some(PALETTE_NAMES[index].to_owned());
+            }
+        }
+        Ok(())
+    }
+
+    /// Start editing the selected setting, or cycle it if it is not text.
+    pub fn edit_setting(&mut self) {
+        let Some(field) = self.settings.as_ref().map(Settings::field) else {
+            return;
+        };
+        if !field.is_text() {
+            self.adjust_setting(1);
+            return;
+        }
+        let value = match field {
+            Field::RefreshInterval => self
+                .config
+                .refresh_interval
+                .map(|secs| secs.to_string())
+                .unwrap_or_default(),
+            Field::Precision => self
+                .precision
+                .map(|precision| precision.to_string())
+                .unwrap_or_default(),
+            _ => self.setting_value(field),
+        };
+        let mut editor = TextArea::new(vec![value]);
+        editor.move_cursor(tui_textarea::CursorMove::End);
+        editor.set_block(search_block(
+            &format!("{} - Enter to apply, Esc to cancel", field.label()),
+            &self.colors,
+        ));
+        editor.set_style(Style::default().fg(self.colors.search_fg));
+        if let Some(settings) = &mut self.settings {
+            settings.editor = Some(editor);
+        }
+    }
+
+    /// Apply the text being edited, keeping the editor open on errors.
+    pub fn commit_setting(&mut self) {
+        let Some(settings) = &mut self.settings else {
+            return;
+        };
+        let Some(editor) = settings.editor.take() else {
+            return;
+        };
+        let field = settings.field();
+        let input = editor.lines().join("");
+        match self.apply_setting(field, &input) {
+            Ok(()) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.error = None;
+                    settings.changed = true;
+                }
+            }
+            Err(e) => {
+                if let Some(settings) = &mut self.settings {
+                    settings.error = Some(e);
+                    settings.editor = Some(editor);
+                }
+            }
+        }
+    }
+
+    /// Step a non-text setting (the theme) forwards or backwards.
+    pub fn adjust_setting(&mut self, delta: isize) {
+        let Some(settings) = &mut self.settings else {
+            return;
+        };
+        if settings.field() != Field::Theme {
+            return;
+        }
+        settings.changed = true;
+        if delta > 0 {
+            self.next_color();
+        } else {
+            self.previous_color();
+        }
+        self.config.theme = Some(PALETTE_NAMES[self.color_index].to_owned());
+    }
+
+    /// Close the settings screen, saving the config if anything changed.
+    pub fn close_settings(&mut self) {
+        let Some(settings) = self.settings.take() else {
+            return;
+        };
+        if settings.changed {
+            if let Err(e) = self.config.save() {
+                self.flash(format!("Unable to save settings: {e}"));
+            }
+        }
+    }
+
+    /// Rebuild the displayed rows from the last fetch and the active watch
+    /// expression, resetting the selection to the top.
+    pub fn apply_filter(&mut self) {
+        self.market_data = filter::filter_rows(&self.rows, self.filter.as_ref());
+        if let Some(quick) = self.quick_filter.and_then(|i| self.quick_filters.get(i)) {
+            let quote = |symbol: &str| match self.quote_asset.as_str() {
+                ALL_QUOTES => quote_of(symbol).unwrap_or_default(),
+                quote => quote,
+            };
+            self.market_data
+                .retain(|row| quick.contains(&row.symbol, quote(&row.symbol)));
+        }
+        match self.view {
+            ViewMode::All => {}
+            ViewMode::Favorites => {
+                let favorites = &self.favorites;
+                self.market_data
+                    .retain(|row| favorites.contains(&row.symbol));
+            }
+            ViewMode::Movers => {
+                self.market_data.retain(|row| row.change_pct.is_some());
+                self.market_data.sort_by(|a, b| {
+                    let size = |row: &MarketRow| row.change_pct.map_or(0.0, f64::abs);
+                    size(b).total_cmp(&size(a))
+                });
+                self.market_data.truncate(TOP_MOVERS);
+            }
+        }
+        if self.only_moved {
+            let history = &self.history;
+            self.market_data
+                .retain(|row| history.get(&row.symbol).is_some_and(price_moved));
+        }
+        self.sort_rows();
+        self.update_lens();
+        self.publish();
+        self.state = self.state.clone().with_selected(0);
+    }
+}
+
+/// What a refresh fetches.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RefreshKind {
+    /// Prices only, bound to `r`; 24h statistics are kept from the last
+    /// full refresh.
+    Prices,
+    /// Prices and 24h statistics, bound to `R`.
+    Full,
+}
+
+/// What became of the 24h statistics in a fetch.
+#[derive(Clone)]
+pub enum StatsFetch {
+    /// A price-only refresh.
+    NotRequested,
+    Fresh,
+    /// The rows came without statistics.
+    Failed(Arc<color_eyre::Report>),
+}
+
+/// The rows a background refresh fetched, or why it failed.
+pub type FetchResult = std::result::Result<(Vec<MarketRow>, StatsFetch), Arc<color_eyre::Report>>;
+
+/// Fetch the rows for `coin`, trying the exact pair first when `exact`.
+/// A failed statistics request does not fail the fetch.
+async fn fetch_rows(
+    kind: RefreshKind,
+    coin: Option<&str>,
+    quote: &str,
+    exact: bool,
+    credentials: Option<&Credentials>,
+) -> Result<(Vec<MarketRow>, StatsFetch)> {
+    // there is no exact pair without a single quote asset
+    if let Some(coin) = coin.filter(|_| exact && quote != ALL_QUOTES) {
+        let (row, stats) = if kind == RefreshKind::Full {
+            let (row, stats) = futures::join!(
+                exact_pair(coin, quote, credentials),
+                pair_stats(coin, quote, credentials)
+            );
+            (row, Some(stats))
+        } else {
+            (exact_pair(coin, quote, credentials).await, None)
+        };
+        match (row?, stats) {
+            (Some(mut row), Some(Ok(stats))) => {
+                row.set_stats(Some(&stats));
+                return Ok((vec![row], StatsFetch::Fresh));
+            }
+            (Some(row), Some(Err(e))) => {
+                return Ok((vec![row], StatsFetch::Failed(Arc::new(e))));
+            }
+            (Some(row), None) => return Ok((vec![row], StatsFetch::NotRequested)),
+            (None, _) => log::info!("{coin}{quote} is not listed, searching by prefix"),
+        }
+    }
+    match kind {
+        RefreshKind::Prices => Ok((
+            market_prices(coin, quote, credentials).await?,
+            StatsFetch::NotRequested,
+        )),
+        RefreshKind::Full => {
+            let (rows, stats) = futures::join!(
+                market_prices(coin, quote, credentials),
+                stats_24h(credentials)
+            );
+            let mut rows = rows?;
+            match stats {
+                Ok(stats) => {
+                    for row in &mut rows {
+                        row.set_stats(stats.get(&row.symbol));
+                    }
+                    Ok((rows, StatsFetch::Fresh))
+                }
+                Err(e) => Ok((rows, StatsFetch::Failed(Arc::new(e)))),
+            }
+        }
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn secs_to_ticks(secs: u32) -> u32 {
+    (f64::from(secs) * TICK_RATE).round() as u32
+}
+
+fn search_block(title: &str, colors: &TableColors) -> Block<'static> {
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(colors.search_border_color))
+        .title(title.to_owned())
+}
+
+/// The favorites' chart history saved at the last quit, with
+/// `persist_history`.
+fn load_history(config: &Config, len: usize) -> history::Histories {
+    if !config.persist_history.unwrap_or(false) {
+        return history::Histories::new();
+    }
+    let Some(path) = history::path() else {
+        return history::Histories::new();
+    };
+    let mut histories = history::load(&path, len);
+    histories.retain(|symbol, _| config.favorites.contains(symbol));
+    histories
+}
+
+// App actions
+// ANCHOR: action_enum
+#[derive(Clone)]
+pub enum Action {
+    NEXT,
+    PREVIOUS,
+    NextColor,
+    PreviousColor,
+    SearchFocus,
+    SearchCoin(String),
+    ApplyFilter(Expr),
+    Refresh,
+    RefreshPrices,
+    ToggleAutoRefresh,
+    TogglePause,
+    ToggleChart,
+    ToggleUpdated,
+    ToggleMoved,
+    CycleView,
+    /// Toggle the quick filter at this index.
+    QuickFilter(usize),
+    OpenStableView,
+    CloseStableView,
+    PickForPair,
+    CloseSideBySide,
+    /// Jump to the next (1) or previous (-1) favorite.
+    JumpToFavorite(isize),
+    ToggleMark,
+    ClearMarks,
+    Export,
+    Copy,
+    CopyTable,
+    CopyJson,
+    DismissFlash,
+    DismissWhatsNew,
+    ResetView,
+    ResetApiUsage,
+    ToggleFavorite,
+    Toggle7d,
+    WeekAgoClose(String, Option<f64>),
+    CycleTimeframe,
+    WindowOpen(Timeframe, String, Option<f64>),
+    WindowOpenThrottled(Timeframe, String),
+    SymbolPrecision(Option<HashMap<String, usize>>),
+    /// The result of the refresh numbered by the count.
+    Fetched(u64, RefreshKind, FetchResult),
+    /// Server minus local clock in milliseconds.
+    ClockOffset(i64),
+    ToggleLog,
+    OpenDigest,
+    OpenAlerts,
+    CloseAlerts,
+    AlertsMove(isize),
+    NewAlert,
+    EditAlert,
+    DeleteAlert,
+    AlertCommit,
+    AlertCancel,
+    OpenPalette,
+    PaletteMove(isize),
+    PaletteRun,
+    ClosePalette,
+    EditNote,
+    NoteCommit,
+    NoteCancel,
+    DismissDigest,
+    OpenInBrowser,
+    ToggleNotation,
+    ToggleInverse,
+    OpenSettings,
+    CloseSettings,
+    SettingsMove(isize),
+    SettingsAdjust(isize),
+    SettingsEdit,
+    SettingsCommit,
+    SettingsCancel,
+    CompareMove(isize),
+    CompareSort,
+    CompareSortDir,
+    ScrollLog(i16),
+    CycleSort,
+    ToggleVolumeSort,
+    ToggleTicker,
+    SelectQuoteTab(usize),
+    ToggleSortDir,
+    Tick,
+    Increment,
+    Decrement,
+    Quit,
+    Render,
+    None,
+}
+// ANCHOR_END: action_enum
+
+// App ui render function
+fn ui(f: &mut Frame, app: &mut App) {
+    let rects = Layout::vertical([Constraint::Min(5), Constraint::Length(3)]).split(f.size());
+
+    app.set_colors();
+    // set again if the tab bar is drawn
+    app.tabs_area = Rect::default();
+
+    if app.compare.is_some() {
+        render_compare(f, app, rects[0]);
+        render_compare_footer(f, app, rects[1]);
+    } else if app.focus_textarea {
+        render_textarea(f, app);
+    } else if !app.loaded {
+        render_startup(f, app);
+    } else if let Some((a, b)) = &app.side_by_side {
+        let [left, right] = Layout::horizontal([Constraint::Percentage(50); 2]).areas(rects[0]);
+        render_coin_pane(f, app, a, left);
+        render_coin_pane(f, app, b, right);
+        render_footer(f, app, rects[1]);
+    } else {
+        let area = if app.quote_tabs.is_empty() {
+            rects[0]
+        } else {
+            let [tabs, area] =
+                Layout::vertical([Constraint::Length(1), Constraint::Min(4)]).areas(rects[0]);
+            render_quote_tabs(f, app, tabs);
+            area
+        };
+        let table_area = if app.show_chart {
+            let [table, chart] =
+                Layout::vertical([Constraint::Min(5), Constraint::Length(CHART_HEIGHT)])
+                    .areas(area);
+            render_chart(f, app, chart);
+            table
+        } else {
+            area
+        };
+        render_table(f, app, table_area);
+
+        // a list that fits would only show a thumb as long as the track
+        if app.market_data.len() > app.visible_rows {
+            render_scrollbar(f, app, table_area);
+        }
+        if app.data_stale {
+            render_stale_banner(f, app, table_area);
+        }
+
+        render_footer(f, app, rects[1]);
+    }
+
+    if app.settings.is_some() {
+        render_settings(f, app);
+    }
+
+    if app.stable_view.is_some() {
+        render_stable_view(f, app);
+    }
+
+    if app.show_log {
+        render_log(f, app);
+    }
+
+    if app.show_digest {
+        render_digest(f, app);
+    }
+
+    if app.alerts_view.is_some() {
+        render_alerts(f, app);
+    }
+
+    if app.palette.is_some() {
+        render_palette(f, app);
+    }
+
+    if let Some((_, editor)) = &app.note_editor {
+        let area = centered_rect(f.size(), 60, 20);
+        f.render_widget(Clear, area);
+        f.render_widget(editor.widget(), area);
+    }
+
+    if !app.whats_new.is_empty() {
+        render_whats_new(f, app);
+    }
+}
+
+/// Lay `app` out again for a terminal resized to `area` and draw it.
+fn redraw_resized<B: Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    area: Rect,
+) -> std::io::Result<()> {
+    terminal.resize(area)?;
+    terminal.draw(|f| ui(f, app))?;
+    app.dirty = false;
+    Ok(())
+}
+
+fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Min(4),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Min(45),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+/// Full-screen loading/error view shown until the first fetch succeeds.
+fn render_startup(f: &mut Frame, app: &App) {
+    let area = centered_rect(f.size(), 60, 20);
+    let (title, lines) = match &app.startup_error {
+        Some(error) => (
+            "Unable to load market data",
+            vec![
+                Line::from(error.as_str()),
+                Line::from(""),
+                Line::from("(r) retry | (Esc) quit"),
+            ],
+        ),
+        None => ("coinmarket", vec![Line::from("Loading market data…")]),
+    };
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: true })
+        .centered()
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup with the notes of the releases since the last run.
+fn render_whats_new(f: &mut Frame, app: &App) {
+    let mut lines = Vec::new();
+    for (version, notes) in &app.whats_new {
+        if !lines.is_empty() {
+            lines.push(Line::from(""));
+        }
+        lines.push(Line::styled(
+            format!("coinmarket {version}"),
+            Style::new().add_modifier(Modifier::BOLD),
+        ));
+        lines.extend(notes.iter().map(|note| Line::from(format!("• {note}"))));
+    }
+    let area = centered_rect(f.size(), 50, 40);
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .block(
+            Block::default()
+                .title("What's new (any key to close)")
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup with each favorite's move since the digest was last dismissed.
+fn render_digest(f: &mut Frame, app: &App) {
+    let digest = app.digest();
+    let lines = if digest.is_empty() {
+        vec![Line::from("No favorites fetched yet, add some with f")]
+    } else {
+        digest
+            .into_iter()
+            .map(|(symbol, baseline, price, change)| {
+                Line::from(vec![
+                    Span::raw(format!(
+                        "{symbol:<14} {} → {}  ",
+                        app.format_price(symbol, baseline),
+                        app.format_price(symbol, price)
+                    )),
+                    Span::styled(
+                        format_change(Some(change)),
+                        change_style(Some(change), &app.colors),
+                    ),
+                ])
+            })
+            .collect()
+    };
+    let title = format!(
+        "Favorites since {} (D/Esc to close and start over)",
+        app.timezone.format_time(&app.digest_since, "%H:%M")
+    );
+    let area = centered_rect(f.size(), 60, 60);
+    let paragraph = Paragraph::new(lines)
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .block(
+            Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup listing the buffered log records, newest first.
+fn render_log(f: &mut Frame, app: &App) {
+    let area = centered_rect(f.size(), 80, 80);
+    let entries = app.log.entries();
+    let lines = if entries.is_empty() {
+        vec![Line::from("Nothing logged yet")]
+    } else {
+        entries
+            .iter()
+            .rev()
+            .map(|entry| {
+                let style = match entry.level {
+                    log::Level::Error => app.colors.down_style,
+                    log::Level::Warn => Style::new().add_modifier(Modifier::BOLD),
+                    _ => Style::new(),
+                };
+                Line::from(vec![
+                    Span::raw(format!(
+                        "{} ",
+                        app.timezone.format_time(&entry.time, "%H:%M:%S")
+                    )),
+                    Span::styled(format!("{:<5} ", entry.level), style),
+                    Span::raw(entry.message.as_str()),
+                ])
+            })
+            .collect()
+    };
+    let paragraph = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.log_scroll, 0))
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .block(
+            Block::default()
+                .title("Log (j/k scroll, Esc close)")
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup with the selected base's price against each stablecoin and the
+/// spread from the first, highlighting spreads beyond `depeg_threshold`.
+fn render_stable_view(f: &mut Frame, app: &App) {
+    let Some(view) = &app.stable_view else {
+        return;
+    };
+    let threshold = app
+        .config
+        .depeg_threshold
+        .unwrap_or(DEFAULT_DEPEG_THRESHOLD);
+    let mut lines = Vec::new();
+    if let Some(error) = &view.error {
+        lines.push(Line::styled(error.as_str(), app.colors.down_style));
+    } else if view.prices.is_empty() {
+        lines.push(Line::from(format!("{} has no stablecoin pairs", view.base)));
+    } else {
+        let reference = &view.prices[0].0;
+        let spreads = view.spreads();
+        for ((quote, price), spread) in view.prices.iter().zip(&spreads) {
+            let symbol = format!("{}{quote}", view.base);
+            let mut line = Line::from(format!(
+                "{quote:<6}  {:>16}  {:>8}",
+                app.format_price(&symbol, *price),
+                format_change(*spread)
+            ));
+            if spread.is_some_and(|spread| spread.abs() > threshold) {
+                line = line.style(change_style(*spread, &app.colors));
+                line.spans.push(Span::raw("  ⚠"));
+            }
+            lines.push(line);
+        }
+        let widest = spreads
+            .iter()
+            .flatten()
+            .fold(0.0, |widest: f64, x| widest.max(x.abs()));
+        lines.push(Line::from(""));
+        lines.push(Line::from(format!(
+            "Spreads vs {reference}, largest {widest:.2}%"
+        )));
+    }
+    let area = centered_rect(f.size(), 40, 40);
+    let paragraph = Paragraph::new(lines)
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .block(
+            Block::default()
+                .title(format!("{} on stablecoins (Esc close)", view.base))
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup listing the settings, with an editor for the one being changed.
+fn render_settings(f: &mut Frame, app: &App) {
+    let Some(settings) = &app.settings else {
+        return;
+    };
+    let area = centered_rect(f.size(), 50, 40);
+    let block = Block::default()
+        .title("Settings (Enter edit, ←/→ theme, Esc save & close)")
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(app.colors.footer_border_color))
+        .border_type(BorderType::Double);
+    let inner = block.inner(area);
+    f.render_widget(Clear, area);
+    f.render_widget(
+        block.style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg)),
+        area,
+    );
+
+    let label_width = Field::ALL
+        .iter()
+        .map(|field| field.label().width())
+        .max()
+        .unwrap_or(0);
+    let mut lines = Field::ALL
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let line = Line::from(format!(
+                "{:<label_width$}  {}",
+                field.label(),
+                app.setting_value(*field)
+            ));
+            if i == settings.selected {
+                line.style(Style::new().add_modifier(Modifier::REVERSED))
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(error) = &settings.error {
+        lines.push(Line::from(""));
+        lines.push(Line::styled(error.as_str(), app.colors.down_style));
+    }
+    let [list, editor] = Layout::vertical([Constraint::Min(1), Constraint::Length(3)]).areas(inner);
+    f.render_widget(Paragraph::new(lines), list);
+    if let Some(textarea) = &settings.editor {
+        f.render_widget(textarea.widget(), editor);
+    }
+}
+
+fn render_quote_tabs(f: &mut Frame, app: &mut App, area: Rect) {
+    app.tabs_area = area;
+    let tabs = Tabs::new(app.quote_tabs.iter().map(String::as_str))
+        .style(
+            Style::new()
+                .fg(app.colors.row_fg)
+                .bg(app.colors.buffer_bg)
+                .add_modifier(Modifier::DIM),
+        )
+        .highlight_style(
+            Style::new()
+                .fg(app.colors.header_fg)
+                .bg(app.colors.header_bg)
+                .add_modifier(Modifier::BOLD),
+        )
+        // past the end, so no tab is highlighted for a quote without one
+        .select(app.quote_tab().unwrap_or(app.quote_tabs.len()));
+    f.render_widget(tabs, area);
+}
+
+/// Grey out the table in `area` and say how old its prices are, so they
+/// are not taken for current ones.
+fn render_stale_banner(f: &mut Frame, app: &App, area: Rect) {
+    f.buffer_mut()
+        .set_style(area, Style::new().add_modifier(Modifier::DIM));
+    let text = format!(
+        "DATA STALE — last update {}s ago",
+        app.last_update.elapsed().as_secs()
+    );
+    let width = u16::try_from(text.width()).unwrap_or(u16::MAX) + 4;
+    let [_, banner, _] = Layout::vertical([
+        Constraint::Fill(1),
+        Constraint::Length(3),
+        Constraint::Fill(1),
+    ])
+    .areas(area);
+    let [_, banner, _] = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(width),
+        Constraint::Fill(1),
+    ])
+    .areas(banner);
+    f.render_widget(Clear, banner);
+    f.render_widget(
+        Paragraph::new(text)
+            .centered()
+            .style(if app.mono {
+                Style::new().add_modifier(Modifier::REVERSED | Modifier::BOLD)
+            } else {
+                Style::new()
+                    .fg(tailwind::WHITE)
+                    .bg(tailwind::RED.c800)
+                    .add_modifier(Modifier::BOLD)
+            })
+            .block(Block::default().borders(Borders::ALL)),
+        banner,
+    );
+}
+
+fn alert_editor(title: &str, value: String, colors: &TableColors) -> TextArea<'static> {
+    let mut editor = TextArea::new(vec![value]);
+    editor.move_cursor(tui_textarea::CursorMove::End);
+    editor.set_block(search_block(
+        &format!("{title} - Enter to save, Esc to cancel"),
+        colors,
+    ));
+    editor.set_style(Style::default().fg(colors.search_fg));
+    editor.set_placeholder_text("e.g. 70000, >70000 or <60000");
+    editor
+}
+
+/// Popup listing the price alerts with their current prices.
+fn render_alerts(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(f.size(), 70, 60);
+    f.render_widget(Clear, area);
+    let prices = app
+        .rows
+        .iter()
+        .map(|row| (row.symbol.as_str(), row.price))
+        .collect::<HashMap<_, _>>();
+    let rows = app
+        .config
+        .alerts
+        .iter()
+        .map(|alert| {
+            let price = prices.get(alert.symbol.as_str()).map_or_else(
+                || "-".to_owned(),
+                |price| app.format_price(&alert.symbol, *price),
+            );
+            let status = if alert.triggered {
+                "triggered"
+            } else {
+                "active"
+            };
+            let row = Row::new(vec![
+                alert.symbol.clone(),
+                app.format_price(&alert.symbol, alert.target),
+                alert.direction.label().to_owned(),
+                price,
+                status.to_owned(),
+            ]);
+            if alert.triggered {
+                row.style(Style::new().add_modifier(Modifier::DIM))
+            } else {
+                row
+            }
+        })
+        .collect::<Vec<_>>();
+    let empty = rows.is_empty();
+    let header = Row::new(["Symbol", "Target", "Side", "Price", "Status"]).style(
+        Style::default()
+            .fg(app.colors.header_fg)
+            .bg(app.colors.header_bg)
+            .add_modifier(app.colors.header_modifier),
+    );
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(2),
+            Constraint::Fill(2),
+            Constraint::Length(6),
+            Constraint::Fill(2),
+            Constraint::Length(9),
+        ],
+    )
+    .header(header)
+    .highlight_style(app.selected_style())
+    .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+    .block(
+        Block::default()
+            .title("Price alerts - (a) add for the selected coin, (e) edit, (d) delete, Esc close")
+            .borders(Borders::ALL)
+            .border_style(Style::new().fg(app.colors.footer_border_color))
+            .border_type(BorderType::Double),
+    );
+    let Some(view) = &mut app.alerts_view else {
+        return;
+    };
+    let [list, message, editor] = Layout::vertical([
+        Constraint::Min(3),
+        Constraint::Length(u16::from(view.error.is_some() || empty)),
+        Constraint::Length(if view.editor.is_some() { 3 } else { 0 }),
+    ])
+    .areas(area);
+    f.render_stateful_widget(table, list, &mut view.state);
+    let style = Style::new().bg(app.colors.buffer_bg);
+    if let Some(error) = &view.error {
+        f.render_widget(
+            Paragraph::new(error.as_str()).style(style.patch(app.colors.down_style)),
+            message,
+        );
+    } else if empty {
+        f.render_widget(
+            Paragraph::new("No alerts yet, press a to add one for the selected coin")
+                .style(style.fg(app.colors.row_fg)),
+            message,
+        );
+    }
+    if let Some(edit) = &view.editor {
+        f.render_widget(edit.input.widget(), editor);
+    }
+}
+
+fn render_palette(f: &mut Frame, app: &App) {
+    let Some(palette) = &app.palette else {
+        return;
+    };
+    let area = centered_rect(f.size(), 50, 50);
+    f.render_widget(Clear, area);
+    let [input, list] = Layout::vertical([Constraint::Length(3), Constraint::Min(1)]).areas(area);
+    f.render_widget(palette.input.widget(), input);
+    let matches = palette.matches();
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(app.colors.footer_border_color))
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg));
+    if matches.is_empty() {
+        f.render_widget(Paragraph::new("No matching command").block(block), list);
+        return;
+    }
+    let items = matches
+        .iter()
+        .map(|(label, _)| ListItem::new(*label))
+        .collect::<Vec<_>>();
+    let mut state = ListState::default().with_selected(Some(palette.selected));
+    f.render_stateful_widget(
+        List::new(items)
+            .block(block)
+            .highlight_style(app.selected_style()),
+        list,
+        &mut state,
+    );
+}
+
+fn render_textarea(f: &mut Frame, app: &mut App) {
+    let area = centered_rect(f.size(), 20, 20);
+    f.render_widget(app.textarea.widget(), area);
+}
+
+fn render_table(f: &mut Frame, app: &mut App, area: Rect) {
+    let header_style = Style::default()
+        .fg(app.colors.header_fg)
+        .bg(app.colors.header_bg)
+        .add_modifier(app.colors.header_modifier);
+    let selected_style = app.selected_style();
+
+    // rows below the header that fit on screen, used to pick 7d symbols
+    app.visible_rows = usize::from(area.height.saturating_sub(1) / app.row_layout.height);
+
+    let columns = fit_columns(app, area.width);
+    app.columns.clone_from(&columns);
+    let header = columns
+        .iter()
+        .map(|column| {
+            Cell::from(Text::from(app.header_title(*column)).alignment(column.alignment()))
+        })
+        .collect::<Row>()
+        .style(header_style)
+        .height(1);
+    // only the rows around the viewport are built, so the offset the table
+    // would pick to keep the selection in view is settled here
+    if let Some(selected) = app.state.selected() {
+        if app.recenter {
+            *app.state.offset_mut() =
+                centered_offset(selected, app.visible_rows, app.market_data.len());
+            app.recenter = false;
+        }
+        let offset = app
+            .state
+            .offset()
+            .min(selected)
+            .max((selected + 1).saturating_sub(app.visible_rows.max(1)));
+        *app.state.offset_mut() = offset;
+    }
+    let widths = columns
+        .iter()
+        .map(|column| Constraint::Min(column.width(app.longest_item_lens)))
+        .collect::<Vec<_>>();
+    // the widths the table will give each column, narrower than asked for
+    // when the terminal is, laid out the way `Table` does
+    let highlight_width = u16::try_from(app.row_layout.highlight_symbol().width()).unwrap_or(0);
+    let [_, columns_area] =
+        Layout::horizontal([Constraint::Length(highlight_width), Constraint::Fill(0)]).areas(area);
+    let column_widths = Layout::horizontal(widths.clone())
+        .spacing(1)
+        .split(columns_area)
+        .iter()
+        .map(|rect| usize::from(rect.width))
+        .collect::<Vec<_>>();
+    let offset = app.state.offset().min(app.market_data.len());
+    let start = offset.saturating_sub(app.render_buffer);
+    let end = (offset + app.visible_rows + app.render_buffer).min(app.market_data.len());
+    let mut window_state = TableState::default()
+        .with_offset(offset - start)
+        .with_selected(app.state.selected().map(|selected| selected - start));
+    let rows = app.market_data[start..end]
+        .iter()
+        .enumerate()
+        .map(|(i, data)| {
+            let color = app.row_color(start + i);
+            let text = |column: Column, content: &str| {
+                app.row_layout.text(content).alignment(column.alignment())
+            };
+            let change_cell = |column: Column, change: Option<f64>| {
+                Cell::from(text(column, &format_change(change)))
+                    .style(change_style(change, &app.colors))
+            };
+            // `fit` cuts what the table would otherwise clip mid-character
+            let cells = columns.iter().zip(&column_widths).map(|(column, &fit)| {
+                let cell = match column {
+                    Column::Gutter => {
+                        let favorite = if app.favorites.contains(&data.symbol) {
+                            "★"
+                        } else {
+                            " "
+                        };
+                        let mark = if app.marked.contains(&data.symbol) {
+                            "✓"
+                        } else {
+                            " "
+                        };
+                        let note = if app.config.notes.contains_key(&data.symbol) {
+                            "✎"
+                        } else {
+                            " "
+                        };
+                        Cell::from(text(*column, &format!("{favorite}{mark}{note}")))
+                    }
+                    Column::Symbol => {
+                        let symbol = truncate_display(&data.symbol, fit);
+                        match app.search_coin.as_deref() {
+                            Some(query) => Cell::from(
+                                app.row_layout
+                                    .line(highlight_matches(&symbol, query))
+                                    .alignment(column.alignment()),
+                            ),
+                            None => Cell::from(text(*column, &symbol)),
+                        }
+                    }
+                    Column::Quote => Cell::from(text(*column, data.quote().unwrap_or("-"))),
+                    Column::Price => Cell::from(text(
+                        *column,
+                        &truncate_display(&app.price_text(&data.symbol, data.price), fit),
+                    ))
+                    .style(app.price_flash_style(&data.symbol)),
+                    Column::Change => change_cell(*column, app.change(data)),
+                    Column::Volume => Cell::from(text(
+                        *column,
+                        &volume_bar(data.quote_volume, app.max_volume, VOLUME_BAR_WIDTH),
+                    )),
+                    Column::Vwap => vwap_cell(data, app.row_layout, &app.colors),
+                    Column::Change7d => change_cell(*column, data.change_7d),
+                    Column::Updated => Cell::from(text(*column, &app.updated_text(&data.symbol))),
+                };
+                // the last statistics fetch failed, so these are older values
+                if app.stats_stale
+                    && column.is_stats()
+                    && (*column != Column::Change || app.timeframe == Timeframe::Day)
+                {
+                    cell.dim()
+                } else {
+                    cell
+                }
+            });
+            Row::new(cells)
+                .style(Style::new().fg(app.colors.row_fg).bg(color))
+                .height(app.row_layout.height)
+        });
+    let t = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(selected_style)
+        .highlight_symbol(app.row_layout.highlight_symbol())
+        .bg(app.colors.buffer_bg)
+        .highlight_spacing(HighlightSpacing::Always);
+    f.render_stateful_widget(t, area, &mut window_state);
+
+    if app.market_data.is_empty() {
+        let message = match (&app.empty_reason, &app.filter) {
+            (Some(reason), _) => reason.clone(),
+            (None, _) if app.only_moved => "No movement since the last update".to_owned(),
+            (None, _) if app.quick_filter.is_some() => {
+                "Nothing in this quick filter is listed, press its key again for all pairs"
+                    .to_owned()
+            }
+            (None, _) if app.view == ViewMode::Favorites => {
+                "No favorites in this search, press V for all pairs".to_owned()
+            }
+            (None, Some(_)) => "No pairs match the filter".to_owned(),
+            (None, None) => format!("No {} pairs listed", app.quote_asset),
+        };
+        let paragraph = Paragraph::new(message)
+            .wrap(Wrap { trim: true })
+            .centered()
+            .style(Style::new().fg(app.colors.row_fg));
+        f.render_widget(paragraph, centered_rect(area, 60, 20));
+    }
+}
+
+/// Table columns, in display order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Column {
+    /// Favorite and mark indicators.
+    Gutter,
+    Symbol,
+    /// Quote asset of each row, shown only when listing every quote asset.
+    Quote,
+    Price,
+    Change,
+    /// 24h quote volume as a bar relative to the busiest pair shown.
+    Volume,
+    Vwap,
+    Change7d,
+    /// When the price last changed, shown only when rows differ.
+    Updated,
+}
+
+impl Column {
+    /// Columns with a lower priority are hidden first when the table does
+    /// not fit; `None` columns are always shown.
+    fn priority(self) -> Option<u8> {
+        match self {
+            Column::Symbol | Column::Price => None,
+            Column::Change => Some(6),
+            Column::Quote => Some(5),
+            Column::Gutter => Some(4),
+            Column::Change7d => Some(3),
+            Column::Volume => Some(2),
+            Column::Vwap => Some(1),
+            Column::Updated => Some(0),
+        }
+    }
+
+    /// Numbers are right-aligned so their digits line up.
+    fn alignment(self) -> Alignment {
+        match self {
+            Column::Price | Column::Change | Column::Change7d | Column::Updated => Alignment::Right,
+            Column::Gutter | Column::Symbol | Column::Quote | Column::Volume | Column::Vwap => {
+                Alignment::Left
+            }
+        }
+    }
+
+    /// Whether the column comes from the 24h statistics.
+    fn is_stats(self) -> bool {
+        matches!(self, Column::Change | Column::Volume | Column::Vwap)
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Column::Gutter => "",
+            Column::Symbol => "Symbol",
+            Column::Quote => "Quote",
+            Column::Price => "Price",
+            Column::Change => "24h %",
+            Column::Volume => "Volume",
+            Column::Vwap => "VWAP",
+            Column::Change7d => "7d %",
+            Column::Updated => "Updated",
+        }
+    }
+
+    fn sort_mode(self) -> SortMode {
+        match self {
+            Column::Symbol => SortMode::Symbol,
+            Column::Price => SortMode::Price,
+            Column::Change => SortMode::Change,
+            Column::Volume => SortMode::Volume,
+            _ => SortMode::None,
+        }
+    }
+
+    /// Minimum width given the longest symbol, price, 24h and 7d change.
+    fn width(self, lens: (u16, u16, u16, u16)) -> u16 {
+        // + 1 is for padding.
+        match self {
+            Column::Gutter => 3,
+            Column::Symbol => lens.0 + 1,
+            Column::Quote => QUOTE_WIDTH + 1,
+            Column::Price => lens.1 + 1,
+            Column::Change => lens.2 + 1,
+            Column::Volume => VOLUME_BAR_WIDTH + 1,
+            Column::Vwap => 5,
+            Column::Change7d => lens.3 + 1,
+            Column::Updated => 9,
+        }
+    }
+}
+
+/// The enabled columns that fit in `width`, dropping the lowest priority
+/// ones first.
+fn fit_columns(app: &App, width: u16) -> Vec<Column> {
+    let mut columns = vec![Column::Gutter, Column::Symbol];
+    if app.quote_asset == ALL_QUOTES {
+        columns.push(Column::Quote);
+    }
+    columns.extend([Column::Price, Column::Change, Column::Volume, Column::Vwap]);
+    if app.show_7d {
+        columns.push(Column::Change7d);
+    }
+    if app.show_updated && app.updates_differ() {
+        columns.push(Column::Updated);
+    }
+    // the highlight symbol always takes its space, plus one between columns
+    let needed = |columns: &[Column]| {
+        let spacing = u16::try_from(columns.len().saturating_sub(1)).unwrap_or(u16::MAX);
+        columns
+            .iter()
+            .map(|column| column.width(app.longest_item_lens))
+            .fold(3 + spacing, u16::saturating_add)
+    };
+    while needed(&columns) > width {
+        let Some(lowest) = columns
+            .iter()
+            .enumerate()
+            .filter_map(|(i, column)| column.priority().map(|p| (p, i)))
+            .min()
+        else {
+            break;
+        };
+        columns.remove(lowest.1);
+    }
+    columns
+}
+
+/// `text` with the parts matching the search `query` in bold and
+/// underlined, to show why a row matched.
+fn highlight_matches(text: &str, query: &str) -> Line<'static> {
+    let style = Style::new().add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut end = 0;
+    for range in filter::match_ranges(text, query) {
+        if range.start > end {
+            spans.push(Span::raw(text[end..range.start].to_owned()));
+        }
+        spans.push(Span::styled(text[range.clone()].to_owned(), style));
+        end = range.end;
+    }
+    if end < text.len() {
+        spans.push(Span::raw(text[end..].to_owned()));
+    }
+    Line::from(spans)
+}
+
+/// Whether the last two price samples differ.
+fn price_moved(samples: &VecDeque<(f64, f64)>) -> bool {
+    let mut latest = samples.iter().rev().map(|(_, price)| price);
+    matches!((latest.next(), latest.next()), (Some(last), Some(previous)) if last != previous)
+}
+
+/// Ring the terminal bell on the terminal the TUI draws to.
+fn ring_bell() {
+    let mut stderr = std::io::stderr();
+    // a terminal without a bell just ignores it
+    let _ = stderr.write_all(b"\x07").and_then(|()| stderr.flush());
+}
+
+/// `volume` as a bar of block glyphs `width` cells long at `max`, in
+/// eighths of a cell. Missing or zero volume is an empty bar.
+fn volume_bar(volume: Option<f64>, max: f64, width: u16) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    let share = match volume {
+        Some(volume) if max > 0.0 => (volume / max).clamp(0.0, 1.0),
+        _ => 0.0,
+    };
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (share * f64::from(width) * 8.0).round() as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+    let mut bar = "█".repeat(full);
+    if partial > 0 {
+        bar.push(PARTIAL[partial]);
+    }
+    bar
+}
+
+/// ▲/▼ for a price above/below the 24h VWAP, blank without one.
+fn vwap_cell(data: &MarketRow, layout: RowLayout, colors: &TableColors) -> Cell<'static> {
+    let (glyph, style) = match data.vs_vwap() {
+        Some(Ordering::Greater) => ("▲", colors.up_style),
+        Some(Ordering::Less) => ("▼", colors.down_style),
+        Some(Ordering::Equal) => ("=", Style::new()),
+        None => ("", Style::new()),
+    };
+    Cell::from(layout.text(glyph)).style(style)
+}
+
+/// First row to draw so that `selected` sits in the middle of `visible` rows,
+/// clamped so the viewport never runs past either end of the list.
+fn centered_offset(selected: usize, visible: usize, len: usize) -> usize {
+    selected
+        .saturating_sub(visible / 2)
+        .min(len.saturating_sub(visible))
+}
+
+fn render_scrollbar(f: &mut Frame, app: &mut App, area: Rect) {
+    // in rows, with one position per viewport offset so the thumb covers
+    // exactly the visible share of the list
+    let len = app.market_data.len();
+    let visible = app.visible_rows.clamp(1, len.max(1));
+    app.scroll_state = app
+        .scroll_state
+        .content_length(len.saturating_sub(visible) + 1)
+        .viewport_content_length(visible)
+        .position(app.state.offset());
+    f.render_stateful_widget(
+        Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(None)
+            .end_symbol(None),
+        area.inner(&Margin {
+            vertical: 1,
+            horizontal: 1,
+        }),
+        &mut app.scroll_state,
+    );
+}
+
+/// Line chart of the selected symbol's recent prices.
+fn render_chart(f: &mut Frame, app: &App, area: Rect) {
+    let Some(row) = app.state.selected().and_then(|i| app.market_data.get(i)) else {
+        return;
+    };
+    let symbol = row.symbol.as_str();
+    let title = match app.config.notes.get(symbol) {
+        Some(note) => format!(" {symbol} ✎ {note} "),
+        None => format!(" {symbol} "),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(app.colors.footer_border_color))
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg));
+    let samples = app
+        .history
+        .get(symbol)
+        .map(|samples| samples.iter().copied().collect::<Vec<_>>())
+        .unwrap_or_default();
+    if samples.len() < 2 {
+        f.render_widget(
+            Paragraph::new("collecting data…").centered().block(block),
+            area,
+        );
+        return;
+    }
+    let (first, last) = (samples[0], samples[samples.len() - 1]);
+    // the 24h range as reference lines; the samples may not reach it yet,
+    // or leave it when the statistics are older than the prices
+    let range = [row.high, row.low]
+        .into_iter()
+        .flatten()
+        .map(|price| vec![(first.0, price), (last.0, price)])
+        .collect::<Vec<_>>();
+    let (low, high) = samples.iter().chain(range.iter().flatten()).fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(low, high), (_, price)| (low.min(*price), high.max(*price)),
+    );
+    // a flat line would otherwise sit on the bottom border
+    let margin = ((high - low) * 0.05).max(high.abs() * 1e-6);
+    let style = change_style(Some(last.1 - first.1), &app.colors);
+    let mut datasets = range
+        .iter()
+        .map(|line| {
+            Dataset::default()
+                .marker(symbols::Marker::Braille)
+                .graph_type(GraphType::Line)
+                .style(
+                    Style::new()
+                        .fg(app.colors.row_fg)
+                        .add_modifier(Modifier::DIM),
+                )
+                .data(line)
+        })
+        .collect::<Vec<_>>();
+    // drawn last so the trend stays on top of the reference lines
+    datasets.push(
+        Dataset::default()
+            .marker(symbols::Marker::Braille)
+            .graph_type(GraphType::Line)
+            .style(style)
+            .data(&samples),
+    );
+    let elapsed = (last.0 - first.0).round();
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .bounds([first.0, last.0])
+                .labels(vec![Span::raw(format!("-{elapsed}s")), Span::raw("now")]),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([low - margin, high + margin])
+                .labels(vec![
+                    Span::raw(app.format_price(symbol, low)),
+                    Span::raw(app.format_price(symbol, high)),
+                ]),
+        );
+    f.render_widget(chart, area);
+}
+
+/// One half of the side-by-side view: `symbol`'s latest figures, where it
+/// sits in its 24h range and a sparkline of its recent prices.
+fn render_coin_pane(f: &mut Frame, app: &App, symbol: &str, area: Rect) {
+    let block = Block::default()
+        .title(format!(" {symbol} (v/Esc close) "))
+        .borders(Borders::ALL)
+        .border_style(Style::new().fg(app.colors.footer_border_color))
+        .border_type(BorderType::Double)
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+    let Some(row) = app.rows.iter().find(|row| row.symbol == symbol) else {
+        f.render_widget(
+            Paragraph::new(format!("{symbol} is not in the current search")).centered(),
+            inner,
+        );
+        return;
+    };
+    let price =
+        |price: Option<f64>| price.map_or_else(|| "-".to_owned(), |p| app.format_price(symbol, p));
+    let range = match row.range_position() {
+        Some(position) => {
+            const WIDTH: usize = 20;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let at = (position * (WIDTH - 1) as f64).round() as usize;
+            let bar = (0..WIDTH)
+                .map(|i| if i == at { '●' } else { '━' })
+                .collect::<String>();
+            format!(
+                "{} {bar} {}  {:.0}%",
+                price(row.low),
+                price(row.high),
+                position * 100.0
+            )
+        }
+        None => "-".to_owned(),
+    };
+    let lines = vec![
+        Line::from(format!("Price     {}", app.format_price(symbol, row.price))),
+        Line::from(vec![
+            Span::raw("24h       "),
+            Span::styled(
+                format_change(row.change_pct),
+                change_style(row.change_pct, &app.colors),
+            ),
+        ]),
+        Line::from(format!("VWAP      {}", price(row.vwap))),
+        Line::from(format!("24h range {range}")),
+    ];
+    let [figures, sparkline] = Layout::vertical([
+        Constraint::Length(lines.len() as u16 + 1),
+        Constraint::Min(1),
+    ])
+    .areas(inner);
+    f.render_widget(Paragraph::new(lines), figures);
+    let samples = app
+        .history
+        .get(symbol)
+        .map(|samples| samples.iter().map(|(_, price)| *price).collect::<Vec<_>>())
+        .unwrap_or_default();
+    let (low, high) = samples
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), price| {
+            (low.min(*price), high.max(*price))
+        });
+    // scaled so the lowest sample still shows as a sliver
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let data = samples
+        .iter()
+        .map(|price| {
+            if high > low {
+                ((price - low) / (high - low) * 99.0) as u64 + 1
+            } else {
+                50
+            }
+        })
+        .collect::<Vec<_>>();
+    let style = match (samples.first(), samples.last()) {
+        (Some(first), Some(last)) => change_style(Some(last - first), &app.colors),
+        _ => Style::new(),
+    };
+    f.render_widget(
+        Sparkline::default().data(&data).max(100).style(style),
+        sparkline,
+    );
+}
+
+fn render_footer(f: &mut Frame, app: &App, area: Rect) {
+    let mut text = app.refresh_status();
+    if app.ticker {
+        text.push_str(" | TICKER");
+    }
+    if app.paused {
+        text.push_str(" | PAUSED");
+        if app.held_rows.is_some() {
+            text.push_str(", (p) to show the update");
+        }
+    }
+    let usage = coinmarket::api_usage();
+    text.push_str(&format!(
+        " | API {} req, weight {}",
+        usage.requests, usage.weight
+    ));
+    let down = app.supervisor.down();
+    if !down.is_empty() {
+        text.push_str(&format!(" | ⚠ {} down", down.join(", ")));
+    }
+    if let Some(quick) = app.quick_filter.and_then(|i| app.quick_filters.get(i)) {
+        text.push_str(&format!(" | {}", quick.name));
+    }
+    match app.view {
+        ViewMode::All => {}
+        ViewMode::Favorites => text.push_str(" | favorites"),
+        ViewMode::Movers => text.push_str(&format!(" | top {TOP_MOVERS} movers")),
+    }
+    if app.only_moved {
+        text.push_str(" | moved only");
+    }
+    if app.credentials.is_some() {
+        // requests are authenticated
+        text.push_str(" | 🔑");
+    }
+    if !app.marked.is_empty() {
+        text.push_str(&format!(" | {} marked", app.marked.len()));
+    }
+    if let Some((message, _)) = &app.flash_message {
+        text.push_str(&format!(" | {message}"));
+    }
+    // the status comes first, the key hints get what is left of the line
+    let status_width = text.width() + " | ⇅".width();
+    let room = usize::from(area.width.saturating_sub(2)).saturating_sub(status_width);
+    let hints = app.legend();
+    let all_width = hints.iter().map(|hint| hint.width() + 3).sum::<usize>();
+    let mut spans = Vec::new();
+    if all_width <= room {
+        spans.extend(
+            hints
+                .into_iter()
+                .flat_map(|hint| [hint, " | "])
+                .map(Span::raw),
+        );
+    } else {
+        let mut room = room.saturating_sub(LEGEND_MORE.width() + 3);
+        for hint in hints {
+            if hint.width() + 3 > room {
+                break;
+            }
+            room -= hint.width() + 3;
+            spans.extend([Span::raw(hint), Span::raw(" | ")]);
+        }
+        spans.extend([Span::raw(LEGEND_MORE), Span::raw(" | ")]);
+    }
+    spans.push(Span::raw(text));
+    // lit only while a request to Binance is in flight
+    let network = if coinmarket::requests_in_flight() > 0 {
+        Style::new().add_modifier(Modifier::BOLD)
+    } else {
+        Style::new().add_modifier(Modifier::DIM)
+    };
+    spans.push(Span::raw(" | "));
+    spans.push(Span::styled("⇅", network));
+    let info_footer = Paragraph::new(Line::from(spans))
+        .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
+        .centered()
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::new().fg(app.colors.footer_border_color))
+                .border_type(BorderType::Double),
+        );
+    f.render_widget(info_footer, area);
+}
+
+/// Table of the `--compare` snapshots with the change between them.
+fn render_compare(f: &mut Frame, app: &mut App, area: Rect) {
+    let Some(view) = &app.compare else {
+        return;
+    };
+    let arrow = if view.descending { "▼" } else { "▲" };
+    let (symbol_title, delta_title) = if view.by_delta {
+        ("Symbol".to_owned(), format!("Δ % {arrow}"))
+    } else {
+        (format!("Symbol {arrow}"), "Δ %".to_owned())
+    };
+    let header = [
+        symbol_title,
+        view.names.0.clone(),
+        view.names.1.clone(),
+        delta_title,
+        String::new(),
+    ]
+    .into_iter()
+    .map(Cell::from)
+    .collect::<Row>()
+    .style(
+        Style::default()
+            .fg(app.colors.header_fg)
+            .bg(app.colors.header_bg)
+            .add_modifier(app.colors.header_modifier),
+    )
+    .height(1);
+    let price = |symbol: &str, price: Option<f64>| {
+        price.map_or_else(|| "-".to_owned(), |p| app.format_price(symbol, p))
+    };
+    let rows = view.rows.iter().enumerate().map(|(i, row)| {
+        let color = app.row_color(i);
+        let delta = row.delta_pct();
+        let marker = match (row.price_a, row.price_b) {
+            (Some(_), None) => "only in A",
+            (None, Some(_)) => "only in B",
+            _ => "",
+        };
+        Row::new([
+            Cell::from(app.row_layout.text(&row.symbol)),
+            Cell::from(app.row_layout.text(&price(&row.symbol, row.price_a))),
+            Cell::from(app.row_layout.text(&price(&row.symbol, row.price_b))),
+            Cell::from(app.row_layout.text(&format_change(delta)))
+                .style(change_style(delta, &app.colors)),
+            Cell::from(app.row_layout.text(marker)),
+        ])
+        .style(Style::new().fg(app.colors.row_fg).bg(color))
+        .height(app.row_layout.height)
+    });
+    let widths = [
+        Constraint::Min(12),
+        Constraint::Min(14),
+        Constraint::Min(14),
+        Constraint::Min(10),
+        Constraint::Min(10),
+    ];
+    let table = Table::new(rows, widths)
+        .header(header)
+        .highlight_style(app.selected_style())
+        .highlight_symbol(app.row_layout.highlight_symbol())
+        .bg(app.colors.buffer_bg)
+        .highlight_spacing(HighlightSpacing::Always);
+    if let Some(view) = &mut app.compare {
+        f.render_stateful_widget(table, area, &mut view.state);
+    }
 }
 
-fn render_footer(f: &mut Frame, app: &App, area: Rect) {
-    let info_footer = Paragraph::new(Line::from(INFO_TEXT))
+fn render_compare_footer(f: &mut Frame, app: &App, area: Rect) {
+    let Some(view) = &app.compare else {
+        return;
+    };
+    let text = format!(
+        "(Esc) quit | (↑) move up | (↓) move down | (S) sort by symbol/delta | (d) sort direction | A = {} | B = {}",
+        view.names.0, view.names.1
+    );
+    let footer = Paragraph::new(Line::from(text))
         .style(Style::new().fg(app.colors.row_fg).bg(app.colors.buffer_bg))
         .centered()
         .block(
@@ -269,10 +4164,34 @@ fn render_footer(f: &mut Frame, app: &App, area: Rect) {
                 .border_style(Style::new().fg(app.colors.footer_border_color))
                 .border_type(BorderType::Double),
         );
-    f.render_widget(info_footer, area);
+    f.render_widget(footer, area);
+}
+
+fn format_change(change: Option<f64>) -> String {
+    match change {
+        Some(change) => format!("{change:+.2}%"),
+        None => "-".to_owned(),
+    }
+}
+
+fn change_style(change: Option<f64>, colors: &TableColors) -> Style {
+    match change {
+        Some(change) if change > 0.0 => colors.up_style,
+        Some(change) if change < 0.0 => colors.down_style,
+        _ => Style::new(),
+    }
 }
 
-fn constraint_len_calculator(items: &[SymbolPrice]) -> (u16, u16) {
+/// Widths of the longest symbol, price, change and 7d change as displayed,
+/// each at least as wide as its header as `title` shows it, sort arrow
+/// included, so a column never collapses, even for values made of
+/// zero-width characters.
+fn constraint_len_calculator(
+    items: &[MarketRow],
+    format_price: impl Fn(&str, f64) -> String,
+    change: impl Fn(&MarketRow) -> Option<f64>,
+    title: impl Fn(Column) -> String,
+) -> (u16, u16, u16, u16) {
     let symbols = items
         .iter()
         .map(|x| x.symbol.as_str())
@@ -281,7 +4200,7 @@ fn constraint_len_calculator(items: &[SymbolPrice]) -> (u16, u16) {
         .unwrap_or(0);
     let string_price = items
         .iter()
-        .map(|x| x.price.to_string())
+        .map(|x| format_price(&x.symbol, x.price))
         .collect::<Vec<String>>();
 
     let price = string_price
@@ -291,28 +4210,177 @@ fn constraint_len_calculator(items: &[SymbolPrice]) -> (u16, u16) {
         .max()
         .unwrap_or(0);
 
-    #[allow(clippy::cast_possible_truncation)]
-    (symbols as u16, price as u16)
+    let change = items
+        .iter()
+        .map(|x| format_change(change(x)).width())
+        .max()
+        .unwrap_or(0);
+
+    let change_7d = items
+        .iter()
+        .map(|x| format_change(x.change_7d).width())
+        .max()
+        .unwrap_or(0);
+
+    let at_least_title = |len: usize, column: Column| {
+        #[allow(clippy::cast_possible_truncation)]
+        (len.max(title(column).width()) as u16)
+    };
+    (
+        at_least_title(symbols, Column::Symbol),
+        at_least_title(price, Column::Price),
+        at_least_title(change, Column::Change),
+        at_least_title(change_7d, Column::Change7d),
+    )
 }
 
 // ANCHOR: get_action
-fn get_action(_app: &App, event: Event) -> Action {
+fn get_action(app: &App, event: Event) -> Action {
+    // from here on navigation is matched on arrows only
+    let event = match event {
+        Event::Key(mut key) => {
+            key.code = app.navigation.translate(key.code);
+            Event::Key(key)
+        }
+        event => event,
+    };
     match event {
         Event::Error => Action::None,
         Event::Tick => Action::Tick,
         Event::Render => Action::Render,
-        Event::Key(key) => {
-            match key.code {
-                Char('q') | Esc => Action::Quit,
-                Char('j') | Down => Action::NEXT,
-                Char('k') | Up => Action::PREVIOUS,
-                Char('l') | Right => Action::NextColor,
-                Char('h') | Left => Action::PreviousColor,
-                Char('s') => Action::SearchFocus,
-                Char('r') => Action::Refresh,
-                _ => Action::None,
-            }
+        // focus events are only reported when `refresh_on_focus` is enabled
+        Event::FocusGained if app.ticks_since_refresh >= secs_to_ticks(FOCUS_REFRESH_DEBOUNCE) => {
+            Action::Refresh
+        }
+        // raw mode delivers Ctrl-C as a key rather than SIGINT
+        Event::Key(key)
+            if key.code == Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) =>
+        {
+            Action::Quit
+        }
+        Event::Key(_) if !app.whats_new.is_empty() => Action::DismissWhatsNew,
+        Event::Key(key) if app.compare.is_some() => match key.code {
+            Char('q') | Esc => Action::Quit,
+            Down => Action::CompareMove(1),
+            Up => Action::CompareMove(-1),
+            Char('S') => Action::CompareSort,
+            Char('d') => Action::CompareSortDir,
+            _ => Action::None,
+        },
+        // snapshots do not change, so there is nothing to refresh
+        _ if app.compare.is_some() => match event {
+            Event::Tick => Action::Tick,
+            Event::Render => Action::Render,
+            _ => Action::None,
+        },
+        Event::Key(key) if app.side_by_side.is_some() => match key.code {
+            Char('q') => Action::Quit,
+            Char('v') | Esc => Action::CloseSideBySide,
+            _ => Action::None,
+        },
+        Event::Key(key) if app.stable_view.is_some() => match key.code {
+            Char('q') => Action::Quit,
+            Char('g') | Esc => Action::CloseStableView,
+            _ => Action::None,
+        },
+        Event::Key(key) if app.alerts_view.is_some() => match key.code {
+            Char('q') => Action::Quit,
+            Char('B') | Esc => Action::CloseAlerts,
+            Down => Action::AlertsMove(1),
+            Up => Action::AlertsMove(-1),
+            Char('a') => Action::NewAlert,
+            Char('e') | Enter => Action::EditAlert,
+            Char('d') | Delete => Action::DeleteAlert,
+            _ => Action::None,
+        },
+        Event::Key(key) if app.show_digest => match key.code {
+            Char('q') => Action::Quit,
+            Char('D') | Esc => Action::DismissDigest,
+            _ => Action::None,
+        },
+        Event::Key(key) if app.show_log => match key.code {
+            Char('q') => Action::Quit,
+            Char('L') | Esc => Action::ToggleLog,
+            Down => Action::ScrollLog(1),
+            Up => Action::ScrollLog(-1),
+            _ => Action::None,
+        },
+        Event::Key(key) if app.settings.is_some() => match key.code {
+            Char('q') => Action::Quit,
+            Char(',') | Esc => Action::CloseSettings,
+            Down => Action::SettingsMove(1),
+            Up => Action::SettingsMove(-1),
+            Right => Action::SettingsAdjust(1),
+            Left => Action::SettingsAdjust(-1),
+            Enter => Action::SettingsEdit,
+            _ => Action::None,
+        },
+        // only retry and quit make sense on the startup screen
+        Event::Key(key) if !app.loaded => match key.code {
+            Char('q') | Esc => Action::Quit,
+            Char('r') => Action::Refresh,
+            Char('L') => Action::ToggleLog,
+            _ => Action::None,
+        },
+        // the tabs are not clickable under a popup
+        Event::Mouse(mouse)
+            if app.loaded
+                && !app.overlay_open()
+                && mouse.kind == MouseEventKind::Down(MouseButton::Left) =>
+        {
+            app.quote_tab_at(mouse.column, mouse.row)
+                .map_or(Action::None, Action::SelectQuoteTab)
         }
+        Event::Key(key) => match key.code {
+            Char('q') | Esc => Action::Quit,
+            Down => Action::NEXT,
+            Up => Action::PREVIOUS,
+            Right if !app.quote_tabs.is_empty() => Action::SelectQuoteTab(app.quote_tab_by(1)),
+            Left if !app.quote_tabs.is_empty() => Action::SelectQuoteTab(app.quote_tab_by(-1)),
+            Right => Action::NextColor,
+            Left => Action::PreviousColor,
+            Char('s') => Action::SearchFocus,
+            Char('r') => Action::RefreshPrices,
+            Char('R') => Action::Refresh,
+            Char('a') => Action::ToggleAutoRefresh,
+            Char('A') => Action::ToggleTicker,
+            Char('p') if key.modifiers.contains(KeyModifiers::CONTROL) => Action::OpenPalette,
+            Char(':' | '?') => Action::OpenPalette,
+            Char('p') => Action::TogglePause,
+            Char('c') => Action::ToggleChart,
+            Char('t') => Action::ToggleUpdated,
+            Char('m') => Action::ToggleMoved,
+            Char('V') => Action::CycleView,
+            Char(digit @ '1'..='9') => Action::QuickFilter(digit as usize - '1' as usize),
+            Char('g') => Action::OpenStableView,
+            Char('v') => Action::PickForPair,
+            Char(']') => Action::JumpToFavorite(1),
+            Char('[') => Action::JumpToFavorite(-1),
+            Char(' ') => Action::ToggleMark,
+            Char('u') => Action::ClearMarks,
+            Char('U') => Action::ResetApiUsage,
+            Char('e') => Action::Export,
+            Char('y') => Action::Copy,
+            Char('Y') => Action::CopyTable,
+            Char('J') => Action::CopyJson,
+            Char('x') => Action::DismissFlash,
+            Char('f') => Action::ToggleFavorite,
+            Char('w') => Action::Toggle7d,
+            Char('T') => Action::CycleTimeframe,
+            Char('o') => Action::OpenInBrowser,
+            Char('E') => Action::ToggleNotation,
+            Char('i') => Action::ToggleInverse,
+            Char(',') => Action::OpenSettings,
+            Char('L') => Action::ToggleLog,
+            Char('D') => Action::OpenDigest,
+            Char('B') => Action::OpenAlerts,
+            Char('n') => Action::EditNote,
+            Char('S') => Action::CycleSort,
+            Char('b') => Action::ToggleVolumeSort,
+            Char('d') => Action::ToggleSortDir,
+            Char('0') => Action::ResetView,
+            _ => Action::None,
+        },
         _ => Action::None,
     }
 }
@@ -335,61 +4403,542 @@ async fn update(app: &mut App<'_>, action: Action) {
         }
         Action::SearchFocus => {
             app.focus_textarea = true;
+            // drop any notice left from the previous search
+            app.textarea
+                .set_block(search_block(SEARCH_TITLE, &app.colors));
         }
         Action::Refresh => {
-            app.market_data = market_data(app.search_coin.as_ref()).await.unwrap();
-            app.scroll_state = app
-                .scroll_state
-                .content_length((app.market_data.len().saturating_sub(1)) * ITEM_HEIGHT);
-            app.longest_item_lens = constraint_len_calculator(&app.market_data);
-            app.state = app.state.clone().with_selected(0);
+            app.refresh(RefreshKind::Full);
+        }
+        Action::RefreshPrices => {
+            app.refresh(RefreshKind::Prices);
+        }
+        Action::Fetched(generation, kind, result) => {
+            let position = app.view_position();
+            app.finish_refresh(generation, kind, result);
+            app.restore_view_position(position);
+        }
+        Action::JumpToFavorite(direction) => {
+            app.jump_to_favorite(direction);
+        }
+        Action::OpenStableView => {
+            app.open_stable_view().await;
+        }
+        Action::CloseStableView => {
+            app.stable_view = None;
+        }
+        Action::PickForPair => {
+            app.pick_for_pair();
+        }
+        Action::CloseSideBySide => {
+            app.side_by_side = None;
+        }
+        Action::CycleView => {
+            app.cycle_view();
+        }
+        Action::QuickFilter(index) => {
+            app.toggle_quick_filter(index);
+        }
+        Action::ToggleMoved => {
+            app.only_moved = !app.only_moved;
+            let position = app.view_position();
+            app.apply_filter();
+            app.restore_view_position(position);
+        }
+        Action::ToggleUpdated => {
+            app.toggle_updated();
+        }
+        Action::ToggleChart => {
+            app.show_chart = !app.show_chart;
+        }
+        Action::TogglePause => {
+            let position = app.view_position();
+            app.toggle_pause();
+            app.restore_view_position(position);
+        }
+        Action::ToggleAutoRefresh => {
+            // the data is as old as it was, and refreshes at the next tick if due
+            app.auto_refresh = !app.auto_refresh;
+        }
+        Action::ToggleMark => {
+            app.toggle_mark();
+        }
+        Action::ClearMarks => {
+            app.marked.clear();
+        }
+        Action::Export => {
+            app.export();
+        }
+        Action::Copy => {
+            app.copy();
+        }
+        Action::CopyTable => {
+            app.copy_table();
+        }
+        Action::CopyJson => {
+            app.copy_json();
+        }
+        Action::DismissFlash => {
+            app.flash_message = None;
+        }
+        Action::DismissWhatsNew => {
+            app.whats_new.clear();
+        }
+        Action::ResetView => {
+            app.reset_view();
+            app.restore_view_position((None, 0));
+            app.refresh(RefreshKind::Full);
+            app.flash("View reset to defaults");
+        }
+        Action::ToggleFavorite => {
+            app.toggle_favorite();
+        }
+        Action::CompareMove(delta) => {
+            if let Some(view) = &mut app.compare {
+                view.move_by(delta);
+            }
+        }
+        Action::CompareSort => {
+            if let Some(view) = &mut app.compare {
+                view.toggle_sort();
+            }
+        }
+        Action::CompareSortDir => {
+            if let Some(view) = &mut app.compare {
+                view.toggle_sort_direction();
+            }
+        }
+        Action::OpenSettings => {
+            app.settings = Some(Settings::default());
+        }
+        Action::CloseSettings => {
+            app.close_settings();
+        }
+        Action::SettingsMove(delta) => {
+            if let Some(settings) = &mut app.settings {
+                settings.move_by(delta);
+            }
+        }
+        Action::SettingsAdjust(delta) => {
+            app.adjust_setting(delta);
+        }
+        Action::SettingsEdit => {
+            app.edit_setting();
+        }
+        Action::SettingsCommit => {
+            app.commit_setting();
+        }
+        Action::SettingsCancel => {
+            if let Some(settings) = &mut app.settings {
+                settings.editor = None;
+                settings.error = None;
+            }
+        }
+        Action::ToggleNotation => {
+            app.notation = app.notation.toggle();
+            app.update_lens();
+        }
+        Action::ToggleInverse => {
+            app.inverse = !app.inverse;
+            app.update_lens();
+        }
+        Action::OpenInBrowser => {
+            app.open_in_browser();
+        }
+        Action::OpenDigest => {
+            app.show_digest = true;
+        }
+        Action::OpenAlerts => {
+            app.open_alerts();
+        }
+        Action::CloseAlerts => {
+            app.alerts_view = None;
+        }
+        Action::AlertsMove(delta) => {
+            app.move_in_alerts(delta);
+        }
+        Action::NewAlert => {
+            app.new_alert();
+        }
+        Action::EditAlert => {
+            app.edit_alert();
+        }
+        Action::DeleteAlert => {
+            app.delete_alert();
+        }
+        Action::AlertCommit => {
+            app.commit_alert();
+        }
+        Action::AlertCancel => {
+            if let Some(view) = &mut app.alerts_view {
+                view.editor = None;
+                view.error = None;
+            }
+        }
+        Action::OpenPalette => {
+            app.open_palette();
+        }
+        Action::PaletteMove(delta) => {
+            if let Some(palette) = &mut app.palette {
+                palette.move_by(delta);
+            }
+        }
+        Action::PaletteRun => {
+            if let Some(action) = app.palette.take().and_then(|palette| palette.chosen()) {
+                let _ = app.action_tx.send(action);
+            }
+        }
+        Action::ClosePalette => {
+            app.palette = None;
+        }
+        Action::EditNote => {
+            app.edit_note();
+        }
+        Action::NoteCommit => {
+            app.commit_note();
+        }
+        Action::NoteCancel => {
+            app.note_editor = None;
+        }
+        Action::DismissDigest => {
+            app.dismiss_digest();
+        }
+        Action::ToggleLog => {
+            app.show_log = !app.show_log;
+            app.log_scroll = 0;
+        }
+        Action::ScrollLog(lines) => {
+            app.log_scroll = app.log_scroll.saturating_add_signed(lines);
+        }
+        Action::Toggle7d => {
+            app.show_7d = !app.show_7d;
+            if app.show_7d {
+                app.request_week_ago();
+            }
+        }
+        Action::WeekAgoClose(symbol, close) => {
+            app.set_week_ago(symbol, close);
+        }
+        Action::ResetApiUsage => {
+            coinmarket::reset_api_usage();
+            app.flash("API usage counter reset");
+        }
+        Action::CycleTimeframe => {
+            app.cycle_timeframe();
+        }
+        Action::WindowOpen(timeframe, symbol, open) => {
+            app.set_window_open(timeframe, symbol, open);
+        }
+        Action::WindowOpenThrottled(timeframe, symbol) => {
+            app.window_open_throttled(timeframe, symbol);
+        }
+        Action::SymbolPrecision(precision) => {
+            app.set_symbol_precision(precision);
+        }
+        Action::ClockOffset(offset) => {
+            app.set_clock_offset(offset);
+        }
+        Action::CycleSort => {
+            app.cycle_sort();
+        }
+        Action::ToggleVolumeSort => {
+            app.toggle_volume_sort();
+        }
+        Action::ToggleTicker => {
+            app.toggle_ticker();
+        }
+        Action::SelectQuoteTab(i) => {
+            app.select_quote_tab(i);
+        }
+        Action::ToggleSortDir => {
+            app.toggle_sort_direction();
+        }
+        Action::Tick => {
+            // shown state a tick may change, to redraw only when it did
+            let shown = |app: &App| {
+                (
+                    app.state.selected(),
+                    app.data_stale,
+                    app.held_rows.is_some(),
+                    app.flash_message.is_some(),
+                )
+            };
+            let before = shown(app);
+            if app
+                .inactivity_timeout
+                .is_some_and(|timeout| app.last_input.elapsed() >= timeout)
+            {
+                log::info!("No input for {:?}, quitting", app.last_input.elapsed());
+                app.close_settings();
+                app.should_quit = true;
+                return;
+            }
+            app.release_held_rows();
+            app.tick_ticker();
+            app.data_stale = app.loaded
+                && app
+                    .max_staleness
+                    .is_some_and(|max| app.last_update.elapsed() >= max);
+            if let Some((_, ticks)) = &mut app.flash_message {
+                *ticks = ticks.saturating_sub(1);
+                if *ticks == 0 {
+                    app.flash_message = None;
+                }
+            }
+            let rows = app.market_data.len();
+            if let Some(metrics) = &mut app.metrics {
+                metrics.tick(rows);
+            }
+            if app.show_7d {
+                // picks up rows scrolled into view since the last tick
+                app.request_week_ago();
+            }
+            app.request_window_opens();
+            app.ticks_since_refresh = app.ticks_since_refresh.saturating_add(1);
+            app.ticks_since_stats = app.ticks_since_stats.saturating_add(1);
+            // a slow refresh is not stacked with another
+            if app.auto_refresh
+                && app.refresh_started.is_none()
+                && app.ticks_since_refresh >= app.refresh_due()
+            {
+                // the statistics may be due less often than the prices
+                let kind = if app.ticks_since_stats >= app.stats_interval {
+                    RefreshKind::Full
+                } else {
+                    RefreshKind::Prices
+                };
+                app.refresh(kind);
+            }
+            // a highlighted price is redrawn once more to clear it
+            let tick = Duration::from_secs_f64(1.0 / TICK_RATE);
+            let animating = app.flash_message.is_some()
+                || app.refresh_started.is_some()
+                || app
+                    .price_flashes
+                    .values()
+                    .any(|(_, at)| at.elapsed() < app.change_flash + tick);
+            if animating || shown(app) != before {
+                app.dirty = true;
+            }
         }
         Action::SearchCoin(coin) => {
+            // unknown names are searched as typed
+            let coin = app.config.alias(&coin).unwrap_or(coin);
             app.search_coin = if coin.is_empty() { None } else { Some(coin) };
-            app.market_data = market_data(app.search_coin.as_ref()).await.unwrap();
-            app.scroll_state = app
-                .scroll_state
-                .content_length((app.market_data.len().saturating_sub(1)) * ITEM_HEIGHT);
-            app.longest_item_lens = constraint_len_calculator(&app.market_data);
-            app.state = app.state.clone().with_selected(0);
-        }
-        Action::Quit => app.should_quit = true,
+            // a new search asks for different rows, so show them
+            app.paused = false;
+            app.held_rows = None;
+            app.filter = None;
+            // a different search is not a listing change
+            app.known_symbols = None;
+            app.refresh(RefreshKind::Full);
+        }
+        Action::ApplyFilter(filter) => {
+            app.filter = Some(filter);
+            app.apply_filter();
+        }
+        Action::Quit => {
+            // settings persist on quit as well as on close
+            app.close_settings();
+            app.should_quit = true;
+        }
         _ => {}
     };
 }
 // ANCHOR_END: update
 
 // ANCHOR: run
-async fn run() -> Result<()> {
+async fn run(args: Args, log: LogBuffer) -> Result<()> {
     let (action_tx, mut action_rx) = mpsc::unbounded_channel(); // new
 
     // ratatui terminal
-    let mut tui = tui::Tui::new()?.tick_rate(1.0).frame_rate(30.0);
+    let config = Config::load();
+    let mut tui = tui::Tui::new()?
+        .tick_rate(TICK_RATE)
+        .frame_rate(30.0)
+        // pasted line breaks would otherwise arrive as Enter and submit early
+        .paste(true)
+        // only the tab bar is clickable, so keep text selectable without one
+        .mouse(!config.quote_tabs.is_empty())
+        .focus(config.refresh_on_focus.unwrap_or(true))
+        .alternate_screen(!args.no_altscreen);
+    // bound before the TUI starts so a taken port is reported plainly
+    let shutdown = CancellationToken::new();
+    let served = SharedRows::default();
+    let server = match args.serve {
+        Some(addr) => Some(server::serve(addr, served.clone(), shutdown.clone())?),
+        None => None,
+    };
+    // likewise an unreadable snapshot
+    let compare = match &args.compare {
+        Some((a, b)) => Some(CompareView::load(a, b)?),
+        None => None,
+    };
+    logger::capture(true);
     tui.enter()?;
     // application state
-    let mut app = App::new().await?;
+    let mut app = App::new(&config, &args, action_tx.clone(), log);
+    if server.is_some() {
+        app.served = Some(served);
+    }
+    if compare.is_some() {
+        app.compare = compare;
+        // snapshots are static, nothing to fetch
+        app.auto_refresh = false;
+    } else {
+        app.check_version();
+        app.check_startup_view();
+    }
+    // show the loading screen before the first (possibly slow) fetch
+    tui.draw(|f| {
+        ui(f, &mut app);
+    })?;
+    if app.compare.is_none() {
+        action_tx.send(Action::Refresh)?;
+        app.check_clock();
+    }
     loop {
         let e = tui.next().await?;
+        // the pointer passing over the screen changes nothing and is no
+        // sign of someone at the keyboard
+        let pointer_moved =
+            matches!(&e, tui::Event::Mouse(mouse) if mouse.kind == MouseEventKind::Moved);
+        // input may edit the search box directly and a resize needs a full
+        // redraw, so any event but a frame or a tick invalidates the screen;
+        // a tick does when it changes what is shown
+        if !matches!(e, tui::Event::Render | tui::Event::Tick) && !pointer_moved {
+            app.dirty = true;
+        }
+        // only the user counts as activity, not ticks or refreshes
+        if !pointer_moved
+            && matches!(
+                e,
+                tui::Event::Key(_) | tui::Event::Mouse(_) | tui::Event::Paste(_)
+            )
+        {
+            app.last_input = Instant::now();
+        }
         match e {
             tui::Event::Quit => action_tx.send(Action::Quit)?,
             tui::Event::Tick => action_tx.send(Action::Tick)?,
             tui::Event::Render => action_tx.send(Action::Render)?,
+            // redraw at the new size right away instead of at the next frame,
+            // which would show the old layout cut off or wrapped meanwhile
+            tui::Event::Resize(width, height) => {
+                redraw_resized(&mut tui, &mut app, Rect::new(0, 0, width, height))?;
+            }
+            tui::Event::FocusGained | tui::Event::Mouse(_) => {
+                action_tx.send(get_action(&app, e))?;
+            }
+            tui::Event::Paste(text) if app.focus_textarea => {
+                let mut lines = text.lines().map(str::trim).filter(|line| !line.is_empty());
+                app.textarea.insert_str(lines.next().unwrap_or_default());
+                if lines.next().is_some() {
+                    app.textarea.set_block(search_block(
+                        "Pasted text had several lines, only the first was kept",
+                        &app.colors,
+                    ));
+                }
+            }
             tui::Event::Key(_) => {
                 if app.focus_textarea {
                     match e.into() {
                         Input {
-                            key: Key::Esc | Key::Enter,
+                            key: key @ (Key::Esc | Key::Enter),
                             ..
                         } => {
-                            app.focus_textarea = false;
-                            action_tx.send(Action::SearchCoin(
-                                app.textarea.lines()[0].trim().to_uppercase().to_owned(),
-                            ))?;
+                            let query = app.textarea.lines()[0].trim().to_owned();
+                            let parsed = if let Some(expr) = query.strip_prefix(FILTER_PREFIX) {
+                                Some(filter::parse(expr).map_err(|e| format!("Filter error: {e}")))
+                            } else {
+                                query.strip_prefix(PRICE_RANGE_PREFIX).map(|range| {
+                                    filter::parse_price_range(range)
+                                        .map_err(|e| format!("Price range error: {e}"))
+                                })
+                            };
+                            if let Some(parsed) = parsed {
+                                match parsed {
+                                    Ok(filter) => {
+                                        app.focus_textarea = false;
+                                        app.textarea
+                                            .set_block(search_block(SEARCH_TITLE, &app.colors));
+                                        action_tx.send(Action::ApplyFilter(filter))?;
+                                    }
+                                    // keep the box open so the expression can be fixed
+                                    Err(err) if key == Key::Enter => {
+                                        app.textarea.set_block(search_block(&err, &app.colors));
+                                    }
+                                    Err(_) => {
+                                        app.focus_textarea = false;
+                                        app.textarea
+                                            .set_block(search_block(SEARCH_TITLE, &app.colors));
+                                    }
+                                }
+                            } else {
+                                app.focus_textarea = false;
+                                action_tx.send(Action::SearchCoin(query.to_uppercase()))?;
+                            }
+                        }
+                        // the query is a single line, so never insert line breaks
+                        Input {
+                            key: Key::Char('m'),
+                            ctrl: true,
+                            ..
                         }
+                        | Input {
+                            key: Key::Char('\n' | '\r'),
+                            ..
+                        } => {}
                         input => {
                             app.textarea.input(input);
                         }
                     }
+                } else if let Some(palette) = &mut app.palette {
+                    match e.into() {
+                        Input {
+                            key: Key::Enter, ..
+                        } => action_tx.send(Action::PaletteRun)?,
+                        Input { key: Key::Esc, .. } => action_tx.send(Action::ClosePalette)?,
+                        Input { key: Key::Down, .. } => action_tx.send(Action::PaletteMove(1))?,
+                        Input { key: Key::Up, .. } => action_tx.send(Action::PaletteMove(-1))?,
+                        input => {
+                            // the matches change, so start from the best one again
+                            if palette.input.input(input) {
+                                palette.selected = 0;
+                            }
+                        }
+                    }
+                } else if let Some(edit) = app.alerts_view.as_mut().and_then(|v| v.editor.as_mut())
+                {
+                    match e.into() {
+                        Input {
+                            key: Key::Enter, ..
+                        } => action_tx.send(Action::AlertCommit)?,
+                        Input { key: Key::Esc, .. } => action_tx.send(Action::AlertCancel)?,
+                        input => {
+                            edit.input.input(input);
+                        }
+                    }
+                } else if let Some((_, editor)) = &mut app.note_editor {
+                    match e.into() {
+                        Input {
+                            key: Key::Enter, ..
+                        } => action_tx.send(Action::NoteCommit)?,
+                        Input { key: Key::Esc, .. } => action_tx.send(Action::NoteCancel)?,
+                        input => {
+                            editor.input(input);
+                        }
+                    }
+                } else if let Some(editor) = app.settings.as_mut().and_then(|s| s.editor.as_mut()) {
+                    match e.into() {
+                        Input {
+                            key: Key::Enter, ..
+                        } => action_tx.send(Action::SettingsCommit)?,
+                        Input { key: Key::Esc, .. } => action_tx.send(Action::SettingsCancel)?,
+                        input => {
+                            editor.input(input);
+                        }
+                    }
                 } else {
                     let action = get_action(&app, e);
                     action_tx.send(action.clone())?;
@@ -398,14 +4947,37 @@ async fn run() -> Result<()> {
             _ => {}
         };
 
+        // draw at most once per batch, however many frames were queued
+        let mut render_pending = false;
         while let Ok(action) = action_rx.try_recv() {
+            // told apart before the update takes the action and its rows
+            let changes = match action {
+                Action::None | Action::Tick => false,
+                Action::Render => {
+                    render_pending = true;
+                    false
+                }
+                _ => true,
+            };
             // application update
-            update(&mut app, action.clone()).await;
-            // render only when we receive Action::Render
-            if let Action::Render = action {
-                tui.draw(|f| {
-                    ui(f, &mut app);
-                })?;
+            let started = Instant::now();
+            update(&mut app, action).await;
+            if let Some(metrics) = &mut app.metrics {
+                metrics.record_update(started);
+            }
+            if changes {
+                app.dirty = true;
+            }
+        }
+        // render only when we received Action::Render and something changed
+        if render_pending && app.dirty {
+            let started = Instant::now();
+            tui.draw(|f| {
+                ui(f, &mut app);
+            })?;
+            app.dirty = false;
+            if let Some(metrics) = &mut app.metrics {
+                metrics.record_render(started);
             }
         }
 
@@ -415,6 +4987,17 @@ async fn run() -> Result<()> {
         }
     }
     tui.exit()?;
+    logger::capture(false);
+    app.save_notes();
+    app.save_history();
+    shutdown.cancel();
+    if let Some(server) = server {
+        let _ = server.await;
+    }
+    if args.no_altscreen && app.loaded && app.compare.is_none() {
+        // the TUI's frames do not survive in the scrollback, this does
+        print!("{}", app.screen_text());
+    }
 
     Ok(())
 }
@@ -423,11 +5006,15 @@ async fn run() -> Result<()> {
 #[tokio::main]
 async fn main() -> Result<()> {
     errors::install_hooks()?;
-    Builder::new()
-        .filter_level(LevelFilter::Info)
-        .parse_default_env()
-        .init();
-    let result = run().await;
+    let args = Args::parse()?;
+    let log = logger::init()?;
+    let result = match &args.command {
+        Some(Command::ExportFavorites(path)) => favorites::export(&Config::load().favorites, path),
+        Some(Command::ImportFavorites(path)) => favorites::import(&mut Config::load(), path).await,
+        Some(Command::Snapshot(path)) => snapshot::take(&Config::load(), path).await,
+        Some(Command::CheckConfig) => Config::check_file(),
+        None => run(args, log).await,
+    };
 
     result?;
 
@@ -435,27 +5022,442 @@ async fn main() -> Result<()> {
 }
 // ANCHOR_END: all
 
-async fn market_data(coin: Option<&String>) -> Result<Vec<SymbolPrice>> {
-    let market: Market = Binance::new(None, None);
-    // Latest price for ALL symbols with USDT as the quote asset
-    match market.get_all_prices().await {
-        Ok(answer) => {
-            let binance::rest_model::Prices::AllPrices(all_symbols) = answer.clone();
-            let coin_by_usdt = all_symbols
-                .into_iter()
-                .filter(|x| match coin {
-                    Some(coin) => x.symbol.starts_with(coin) && x.symbol.ends_with("USDT"),
-                    None => x.symbol.ends_with("USDT"),
-                })
-                .collect::<Vec<SymbolPrice>>();
-            // info!("{:#?}", coin_by_usdt);
-            Ok(coin_by_usdt)
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(symbol: &str, price: f64, change_pct: Option<f64>) -> MarketRow {
+        MarketRow {
+            symbol: symbol.to_owned(),
+            price,
+            change_pct,
+            change_7d: None,
+            vwap: None,
+            quote_volume: None,
+            high: None,
+            low: None,
+        }
+    }
+
+    /// An app built from `config` and `args` showing `rows` as if just
+    /// fetched. Actions it sends are dropped.
+    fn fixture_app_with(config: &Config, args: &Args, rows: Vec<MarketRow>) -> App<'static> {
+        let (action_tx, _action_rx) = mpsc::unbounded_channel();
+        let mut app = App::new(config, args, action_tx, LogBuffer::default());
+        app.set_rows(rows);
+        app
+    }
+
+    fn fixture_app(rows: Vec<MarketRow>) -> App<'static> {
+        fixture_app_with(&Config::default(), &Args::default(), rows)
+    }
+
+    /// Draw `app` once on a `width` x `height` screen.
+    fn render(app: &mut App, width: u16, height: u16) -> Buffer {
+        let mut terminal = Terminal::new(backend::TestBackend::new(width, height)).unwrap();
+        terminal.draw(|f| ui(f, app)).unwrap();
+        terminal.backend().buffer().clone()
+    }
+
+    /// The text on line `y`, without trailing blanks.
+    fn line(buffer: &Buffer, y: u16) -> String {
+        (0..buffer.area.width)
+            .map(|x| buffer.get(x, y).symbol())
+            .collect::<String>()
+            .trim_end()
+            .to_owned()
+    }
+
+    fn find(buffer: &Buffer, text: &str) -> Option<(u16, u16)> {
+        (0..buffer.area.height).find_map(|y| {
+            let line = line(buffer, y);
+            let byte = line.find(text)?;
+            Some((u16::try_from(line[..byte].width()).unwrap(), y))
+        })
+    }
+
+    #[test]
+    fn mono_mode_emits_no_colors() {
+        let colors = TableColors::mono();
+        let plain = [
+            colors.buffer_bg,
+            colors.header_bg,
+            colors.header_fg,
+            colors.row_fg,
+            colors.selected_style_fg,
+            colors.normal_row_color,
+            colors.alt_row_color,
+            colors.footer_border_color,
+            colors.search_border_color,
+            colors.search_fg,
+        ];
+        assert!(plain.iter().all(|color| *color == Color::Reset));
+
+        let up = change_style(Some(2.5), &colors);
+        let down = change_style(Some(-1.25), &colors);
+        for style in [up, down, change_style(None, &colors)] {
+            assert_eq!((style.fg, style.bg), (None, None), "{style:?}");
+        }
+        // direction is still told apart, by modifiers
+        assert_ne!(up, down);
+        assert_ne!(colors.header_modifier, Modifier::empty());
+    }
+
+    #[test]
+    fn mono_mode_renders_without_colors() {
+        let args = Args {
+            mono: true,
+            ..Args::default()
+        };
+        let mut app = fixture_app_with(
+            &Config::default(),
+            &args,
+            vec![
+                row("BTCUSDT", 65000.5, Some(2.5)),
+                row("ETHUSDT", 3500.0, Some(-1.25)),
+            ],
+        );
+        // by price, high to low
+        app.cycle_sort();
+        app.cycle_sort();
+        app.state.select(Some(1));
+        let buffer = render(&mut app, 80, 24);
+
+        for cell in &buffer.content {
+            assert_eq!((cell.fg, cell.bg), (Color::Reset, Color::Reset), "{cell:?}");
+        }
+        let (_, header) = find(&buffer, "Price ▼").expect("no sort arrow");
+        let (x, selected) = find(&buffer, "ETHUSDT").unwrap();
+        let (_, other) = find(&buffer, "BTCUSDT").unwrap();
+        assert!(header < other && other < selected);
+        let reversed = |y| buffer.get(x, y).modifier.contains(Modifier::REVERSED);
+        assert!(reversed(selected));
+        assert!(!reversed(other));
+    }
+
+    #[test]
+    fn columns_are_never_narrower_than_their_header() {
+        let lens = constraint_len_calculator(
+            &[row("\u{200b}\u{200d}\u{feff}", 1.5, None)],
+            |_, price| format!("{price:.4}"),
+            |row| row.change_pct,
+            |column| column.title().to_owned(),
+        );
+        assert_eq!(lens, (6, 6, 5, 4));
+        assert_eq!(
+            constraint_len_calculator(
+                &[],
+                |_, _| String::new(),
+                |_| None,
+                |column| column.title().to_owned()
+            ),
+            (6, 5, 5, 4)
+        );
+    }
+
+    #[test]
+    fn columns_fit_the_sort_arrow() {
+        let mut app = fixture_app(vec![row("BTCUSDT", 65000.5, Some(1.0))]);
+        for _ in 0..3 {
+            app.cycle_sort();
+        }
+        assert_eq!(app.sort.mode, SortMode::Change);
+        assert_eq!(app.longest_item_lens.2, 7);
+    }
+
+    #[test]
+    fn a_resize_recomputes_the_layout() {
+        let mut app = fixture_app(
+            (0..100)
+                .map(|i| row(&format!("COIN{i}USDT"), f64::from(i), None))
+                .collect(),
+        );
+        let mut terminal = Terminal::new(backend::TestBackend::new(80, 24)).unwrap();
+        terminal.draw(|f| ui(f, &mut app)).unwrap();
+        let small = app.visible_rows;
+
+        app.dirty = true;
+        terminal.backend_mut().resize(120, 48);
+        redraw_resized(&mut terminal, &mut app, Rect::new(0, 0, 120, 48)).unwrap();
+        assert!(!app.dirty);
+        assert!(
+            app.visible_rows > small,
+            "{} rows after {small}",
+            app.visible_rows
+        );
+        // the footer moved to the new bottom edge
+        let buffer = terminal.backend().buffer();
+        assert_eq!(buffer.area, Rect::new(0, 0, 120, 48));
+        assert_eq!(buffer.get(0, 47).symbol(), "╚");
+    }
+
+    #[test]
+    fn a_list_that_fits_has_no_scrollbar() {
+        let thumb_rows = |buffer: &Buffer| {
+            (1..20)
+                .filter(|y| buffer.get(78, *y).symbol() == "█")
+                .count()
+        };
+
+        let mut app = fixture_app(vec![row("BTCUSDT", 1.0, None)]);
+        assert_eq!(thumb_rows(&render(&mut app, 80, 24)), 0);
+
+        let mut app = fixture_app(
+            (0..100)
+                .map(|i| row(&format!("COIN{i}USDT"), 1.0, None))
+                .collect(),
+        );
+        assert!(thumb_rows(&render(&mut app, 80, 24)) > 0);
+    }
+
+    #[test]
+    fn flipping_the_sort_keeps_the_selected_coin() {
+        let mut app = fixture_app(vec![
+            row("BTCUSDT", 65000.5, None),
+            row("ETHUSDT", 3500.0, None),
+            row("SOLUSDT", 150.0, None),
+        ]);
+        app.cycle_sort();
+        app.cycle_sort();
+        assert_eq!(app.sort.mode, SortMode::Price);
+        app.state.select(Some(0));
+        let selected = app.market_data[0].symbol.clone();
+
+        app.toggle_sort_direction();
+        let now = &app.market_data[app.state.selected().unwrap()];
+        assert_eq!(now.symbol, selected);
+        assert_ne!(app.market_data[0].symbol, selected);
+    }
+
+    #[test]
+    fn window_opens_arriving_resort_the_rows() {
+        let mut app = fixture_app(vec![
+            row("BTCUSDT", 65000.5, None),
+            row("ETHUSDT", 3500.0, None),
+        ]);
+        app.timeframe = Timeframe::Hour;
+        for _ in 0..3 {
+            app.cycle_sort();
         }
-        Err(e) => {
-            Err(color_eyre::eyre::eyre!(
-                "Unable to get market data: {:#?}",
-                e
-            )) // Use the eyre macro to create the error
+        assert_eq!(app.sort.mode, SortMode::Change);
+        app.state.select(Some(0));
+        let selected = app.market_data[0].symbol.clone();
+
+        app.set_window_open(Timeframe::Hour, "BTCUSDT".to_owned(), Some(65000.5));
+        app.set_window_open(Timeframe::Hour, "ETHUSDT".to_owned(), Some(3000.0));
+        assert_eq!(app.market_data[0].symbol, "ETHUSDT");
+        let now = &app.market_data[app.state.selected().unwrap()];
+        assert_eq!(now.symbol, selected);
+    }
+
+    #[test]
+    fn a_superseded_refresh_is_dropped() {
+        let mut app = fixture_app(vec![row("BTCUSDT", 65000.5, None)]);
+        app.refresh_generation = 2;
+        app.refresh_started = Some(Instant::now());
+        let fetched = |symbol| Ok((vec![row(symbol, 1.0, None)], StatsFetch::NotRequested));
+
+        app.finish_refresh(1, RefreshKind::Prices, fetched("ETHUSDT"));
+        assert_eq!(app.market_data[0].symbol, "BTCUSDT");
+        assert!(app.refresh_started.is_some());
+
+        app.finish_refresh(2, RefreshKind::Prices, fetched("SOLUSDT"));
+        assert_eq!(app.market_data[0].symbol, "SOLUSDT");
+        assert!(app.refresh_started.is_none());
+    }
+
+    #[test]
+    fn jumping_to_a_favorite_pauses_the_ticker() {
+        let mut app = fixture_app(vec![
+            row("BTCUSDT", 65000.5, None),
+            row("ETHUSDT", 3500.0, None),
+        ]);
+        app.favorites.insert("ETHUSDT".to_owned());
+        app.toggle_ticker();
+        app.jump_to_favorite(1);
+        assert_eq!(app.state.selected(), Some(1));
+        assert_eq!(app.ticker_wait, secs_to_ticks(TICKER_MANUAL_PAUSE));
+    }
+
+    #[tokio::test]
+    async fn sorting_a_long_list_by_the_window_bounds_the_lookups() {
+        let rows = (0..500)
+            .map(|i| row(&format!("COIN{i}USDT"), 1.0, None))
+            .collect();
+        let mut app = fixture_app(rows);
+        app.timeframe = Timeframe::Hour;
+        for _ in 0..3 {
+            app.cycle_sort();
         }
+        assert_eq!(app.sort.mode, SortMode::Change);
+        app.request_window_opens();
+        app.request_window_opens();
+        assert_eq!(app.window_opens_pending.len(), MAX_WINDOW_OPENS_IN_FLIGHT);
+
+        // a throttled lookup is not cached and holds the others back
+        let pending = app.window_opens_pending.iter().next().cloned().unwrap();
+        app.window_open_throttled(pending.0, pending.1.clone());
+        app.request_window_opens();
+        assert!(!app.window_opens_pending.contains(&pending));
+        assert_eq!(
+            app.window_opens_pending.len(),
+            MAX_WINDOW_OPENS_IN_FLIGHT - 1
+        );
+        assert!(app
+            .window_opens
+            .get(&Timeframe::Hour)
+            .is_none_or(|cached| cached.peek(&pending.1).is_none()));
+    }
+
+    #[test]
+    fn tabs_under_a_popup_take_no_clicks() {
+        let config = Config {
+            quote_tabs: vec!["USDT".to_owned(), "BTC".to_owned()],
+            ..Config::default()
+        };
+        let mut app = fixture_app_with(&config, &Args::default(), vec![row("ETHBTC", 0.05, None)]);
+        render(&mut app, 80, 24);
+        let click = |app: &App| {
+            get_action(
+                app,
+                Event::Mouse(crossterm::event::MouseEvent {
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    column: app.tabs_area.x + 1,
+                    row: app.tabs_area.y,
+                    modifiers: KeyModifiers::NONE,
+                }),
+            )
+        };
+        assert!(matches!(click(&app), Action::SelectQuoteTab(0)));
+        app.show_log = true;
+        assert!(matches!(click(&app), Action::None));
+    }
+
+    #[test]
+    fn the_legend_fits_an_80_column_footer() {
+        let mut app = fixture_app(vec![row("BTCUSDT", 65000.5, None)]);
+        assert!(!app.legend().contains(&"(x) dismiss"));
+        let buffer = render(&mut app, 80, 24);
+        let footer = line(&buffer, 22);
+        assert!(footer.contains("(Esc) quit | "), "{footer}");
+        assert!(footer.contains(&format!("{LEGEND_MORE} | ")), "{footer}");
+        // the status is not cut off
+        assert!(footer.contains("weight 0 | ⇅"), "{footer}");
+    }
+
+    #[tokio::test]
+    async fn an_idle_tick_leaves_the_screen_alone() {
+        let mut app = fixture_app(vec![row("BTCUSDT", 65000.5, None)]);
+        app.dirty = false;
+        update(&mut app, Action::Tick).await;
+        assert!(!app.dirty);
+
+        app.flash("Copied");
+        update(&mut app, Action::Tick).await;
+        assert!(app.dirty);
+    }
+
+    #[test]
+    fn an_unloadable_config_skips_the_version_check() {
+        let config = Config {
+            load_failed: true,
+            last_version: Some("0.0.1".to_owned()),
+            ..Config::default()
+        };
+        let mut app = fixture_app_with(&config, &Args::default(), Vec::new());
+        app.check_version();
+        assert!(app.whats_new.is_empty());
+        assert_eq!(app.config.last_version.as_deref(), Some("0.0.1"));
+    }
+
+    #[test]
+    fn a_narrow_screen_cuts_cells_with_an_ellipsis() {
+        let mut app = fixture_app(vec![row("VERYLONGNAMEDCOINUSDT", 65000.5, None)]);
+        let buffer = render(&mut app, 30, 24);
+        let text = line(&buffer, find(&buffer, "VERY").unwrap().1);
+        assert!(text.contains("VERYLONGNAMED…"), "{text}");
+        assert!(text.contains("65,000.5000"), "{text}");
+    }
+
+    #[test]
+    fn spreads_need_a_reference_price() {
+        let view = |prices: [(&str, f64); 2]| StableView {
+            base: "BTC".to_owned(),
+            prices: prices
+                .map(|(quote, price)| (quote.to_owned(), price))
+                .to_vec(),
+            error: None,
+        };
+        assert_eq!(
+            view([("USDT", 100.0), ("USDC", 101.0)]).spreads(),
+            [Some(0.0), Some(1.0)]
+        );
+        assert_eq!(
+            view([("USDT", 0.0), ("USDC", 101.0)]).spreads(),
+            [None, None]
+        );
+    }
+
+    #[test]
+    fn rows_line_up_under_their_headers() {
+        let mut app = fixture_app(vec![
+            row("BTCUSDT", 65000.5, Some(2.5)),
+            row("ETHUSDT", 3500.0, Some(-1.25)),
+        ]);
+        let buffer = render(&mut app, 80, 24);
+        let header = line(&buffer, 0);
+        let titles =
+            ["Symbol", "Price", "24h %", "Volume", "VWAP"].map(|title| header.find(title).unwrap());
+        assert!(titles.windows(2).all(|pair| pair[0] < pair[1]), "{header}");
+
+        let end = |text: &str| {
+            let (x, y) = find(&buffer, text).unwrap();
+            (x + u16::try_from(text.width()).unwrap(), y)
+        };
+        // numbers are right-aligned with their header, names left-aligned
+        assert_eq!(end("65,000.5000").0, end("Price").0);
+        assert_eq!(end("3,500.0000").0, end("Price").0);
+        assert_eq!(end("-1.25%").0, end("24h %").0);
+        assert_eq!(
+            find(&buffer, "BTCUSDT").unwrap().0,
+            find(&buffer, "Symbol").unwrap().0
+        );
+        // each row takes the default three lines, content below the padding
+        assert_eq!(find(&buffer, "BTCUSDT").unwrap().1, 2);
+        assert_eq!(find(&buffer, "ETHUSDT").unwrap().1, 5);
+    }
+
+    #[test]
+    fn an_empty_list_says_why() {
+        let mut app = fixture_app(Vec::new());
+        assert!(find(&render(&mut app, 80, 24), "No USDT pairs listed").is_some());
+
+        let mut app = fixture_app(Vec::new());
+        app.only_moved = true;
+        app.apply_filter();
+        let buffer = render(&mut app, 80, 24);
+        assert!(find(&buffer, "No movement since the last update").is_some());
+    }
+
+    #[test]
+    fn changes_are_colored_by_direction() {
+        // the first row is selected and highlighted instead
+        let rows = || {
+            vec![
+                row("NEWUSDT", 1.0, None),
+                row("BTCUSDT", 65000.5, Some(2.5)),
+                row("ETHUSDT", 3500.0, Some(-1.25)),
+            ]
+        };
+        let cell_style = |buffer: &Buffer, text: &str| {
+            let (x, y) = find(buffer, text).unwrap();
+            buffer.get(x, y).style()
+        };
+
+        let mut app = fixture_app(rows());
+        let buffer = render(&mut app, 80, 24);
+        assert_eq!(cell_style(&buffer, "+2.50%").fg, app.colors.up_style.fg);
+        assert_eq!(cell_style(&buffer, "-1.25%").fg, app.colors.down_style.fg);
+        assert_ne!(app.colors.up_style.fg, app.colors.down_style.fg);
     }
 }
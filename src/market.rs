@@ -0,0 +1,889 @@
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    fmt,
+    sync::atomic::{AtomicU64, AtomicUsize, Ordering::Relaxed},
+    time::Duration,
+};
+
+use binance::api::*;
+use binance::market::*;
+use binance::rest_model::{string_or_float, Prices, SymbolPrice};
+use color_eyre::eyre::Result;
+use serde::{Deserialize, Serialize};
+
+/// A market symbol with its latest price and 24h statistics.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MarketRow {
+    pub symbol: String,
+    pub price: f64,
+    /// 24h price change in percent, if the stats endpoint reported one.
+    #[serde(default)]
+    pub change_pct: Option<f64>,
+    /// 7 day price change in percent, filled in by the caller from
+    /// [`week_ago_close`] since it costs one request per symbol.
+    #[serde(default)]
+    pub change_7d: Option<f64>,
+    /// 24h volume weighted average price, `None` when the pair did not trade.
+    #[serde(default)]
+    pub vwap: Option<f64>,
+    /// 24h traded volume in the quote asset.
+    #[serde(default)]
+    pub quote_volume: Option<f64>,
+    /// 24h highest price, `None` when the pair did not trade.
+    #[serde(default)]
+    pub high: Option<f64>,
+    /// 24h lowest price, `None` when the pair did not trade.
+    #[serde(default)]
+    pub low: Option<f64>,
+}
+
+/// Quote asset that lists the pairs of every quote asset, each row showing
+/// its own quote in a column.
+pub const ALL_QUOTES: &str = "*";
+
+/// Quote assets Binance lists pairs against, used to split a symbol into
+/// base and quote when the table mixes quote assets.
+const KNOWN_QUOTES: &[&str] = &[
+    "USDT", "USDC", "FDUSD", "TUSD", "BUSD", "DAI", "USDP", "PAX", "BTC", "ETH", "BNB", "XRP",
+    "TRX", "DOGE", "DOT", "EUR", "AEUR", "EURI", "GBP", "TRY", "BRL", "ARS", "MXN", "COP", "JPY",
+    "AUD", "RUB", "UAH", "ZAR", "IDR", "BIDR", "NGN", "PLN", "RON", "CZK",
+];
+
+/// The quote asset `symbol` ends with, the longest if several do, e.g.
+/// `FDUSD` for `BTCFDUSD`; `None` for quotes that are not known.
+pub fn quote_of(symbol: &str) -> Option<&'static str> {
+    KNOWN_QUOTES
+        .iter()
+        .filter(|quote| symbol.len() > quote.len() && symbol.ends_with(*quote))
+        .max_by_key(|quote| quote.len())
+        .copied()
+}
+
+impl MarketRow {
+    /// The quote asset the row is priced in, see [`quote_of`].
+    pub fn quote(&self) -> Option<&'static str> {
+        quote_of(&self.symbol)
+    }
+
+    /// Set [`MarketRow::change_7d`] from the close price seven days ago.
+    pub fn set_week_ago_close(&mut self, close: f64) {
+        self.change_7d = (close > 0.0).then(|| (self.price - close) / close * 100.0);
+    }
+
+    /// Fill in the 24h statistics, or blank them without any.
+    pub fn set_stats(&mut self, stats: Option<&Stats>) {
+        self.change_pct = stats.map(|s| s.change_pct);
+        // Binance reports 0 for pairs without trades in the window
+        self.vwap = stats.map(|s| s.vwap).filter(|vwap| *vwap > 0.0);
+        self.quote_volume = stats.map(|s| s.quote_volume);
+        self.high = stats.map(|s| s.high).filter(|high| *high > 0.0);
+        self.low = stats.map(|s| s.low).filter(|low| *low > 0.0);
+    }
+
+    /// Where the price sits in the 24h range, from 0 at the low to 1 at
+    /// the high; `None` without a range.
+    pub fn range_position(&self) -> Option<f64> {
+        let (high, low) = (self.high?, self.low?);
+        (high > low).then(|| ((self.price - low) / (high - low)).clamp(0.0, 1.0))
+    }
+
+    /// How the price compares to the 24h VWAP, if there is one.
+    pub fn vs_vwap(&self) -> Option<Ordering> {
+        self.vwap.and_then(|vwap| self.price.partial_cmp(&vwap))
+    }
+}
+
+/// A Binance API key pair. Requests made with one get the account's higher
+/// rate limits. The secret never appears in `Debug` output.
+#[derive(Clone, PartialEq)]
+pub struct Credentials {
+    api_key: String,
+    secret_key: String,
+}
+
+impl Credentials {
+    pub fn new(api_key: impl Into<String>, secret_key: impl Into<String>) -> Self {
+        Self {
+            api_key: api_key.into(),
+            secret_key: secret_key.into(),
+        }
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials").finish_non_exhaustive()
+    }
+}
+
+/// Returned (inside the `eyre` report) when Binance rejects the
+/// [`Credentials`], so the caller can retry anonymously.
+#[derive(Debug)]
+pub struct InvalidCredentials(String);
+
+impl fmt::Display for InvalidCredentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Binance rejected the API key: {}", self.0)
+    }
+}
+
+impl std::error::Error for InvalidCredentials {}
+
+static REQUESTS: AtomicU64 = AtomicU64::new(0);
+static WEIGHT: AtomicU64 = AtomicU64::new(0);
+static IN_FLIGHT: AtomicUsize = AtomicUsize::new(0);
+
+/// Requests sent to Binance since the start or the last
+/// [`reset_api_usage`], and the request weight Binance counts for them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ApiUsage {
+    pub requests: u64,
+    pub weight: u64,
+}
+
+/// Totals of every request made through this crate, from any task.
+pub fn api_usage() -> ApiUsage {
+    ApiUsage {
+        requests: REQUESTS.load(Relaxed),
+        weight: WEIGHT.load(Relaxed),
+    }
+}
+
+pub fn reset_api_usage() {
+    REQUESTS.store(0, Relaxed);
+    WEIGHT.store(0, Relaxed);
+}
+
+/// Requests to Binance that have not finished yet, from any task.
+pub fn requests_in_flight() -> usize {
+    IN_FLIGHT.load(Relaxed)
+}
+
+/// A request counted in [`requests_in_flight`] until dropped.
+struct InFlight;
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        IN_FLIGHT.fetch_sub(1, Relaxed);
+    }
+}
+
+/// Count one request of `weight`, as documented per endpoint by Binance;
+/// failed requests count too since Binance charges them as well. The
+/// request is in flight until the returned guard is dropped.
+#[must_use]
+fn start_request(weight: u64) -> InFlight {
+    REQUESTS.fetch_add(1, Relaxed);
+    WEIGHT.fetch_add(weight, Relaxed);
+    IN_FLIGHT.fetch_add(1, Relaxed);
+    InFlight
+}
+
+fn market(credentials: Option<&Credentials>) -> Market {
+    match credentials {
+        Some(c) => Binance::new(Some(c.api_key.clone()), Some(c.secret_key.clone())),
+        None => Binance::new(None, None),
+    }
+}
+
+/// Wrap `e` in an `eyre` report with `context`, keeping credential failures
+/// recognisable as [`InvalidCredentials`].
+fn report(context: &str, e: &binance::errors::Error) -> color_eyre::eyre::Report {
+    use binance::errors::Error;
+    match e {
+        Error::Unauthorized | Error::InvalidHeaderError(_) => {
+            color_eyre::eyre::Report::new(InvalidCredentials(e.to_string()))
+        }
+        // -2008 invalid key id, -2014 malformed key, -2015 invalid key/IP/permissions
+        Error::BinanceError { response } if matches!(response.code, -2008 | -2014 | -2015) => {
+            color_eyre::eyre::Report::new(InvalidCredentials(e.to_string()))
+        }
+        _ if matches!(response_status(e), Some(429 | 418)) => {
+            color_eyre::eyre::Report::new(RateLimited(format!("{context}: {}", describe_error(e))))
+        }
+        e => color_eyre::eyre::eyre!("{context}: {}", describe_error(e)),
+    }
+}
+
+/// HTTP status of a response the client has no error of its own for, which
+/// it only reports as `Received response: <status>`.
+fn response_status(e: &binance::errors::Error) -> Option<u16> {
+    match e {
+        binance::errors::Error::Msg(msg) => msg.strip_prefix("Received response: ")?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returned (inside the `eyre` report) when Binance throttles a request.
+#[derive(Debug)]
+pub struct RateLimited(String);
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for RateLimited {}
+
+/// The subset of `/api/v3/ticker/24hr` we display alongside the price.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Stats {
+    #[serde(rename = "priceChangePercent", with = "string_or_float")]
+    pub change_pct: f64,
+    /// Volume weighted average price, 0 without trades.
+    #[serde(rename = "weightedAvgPrice", with = "string_or_float")]
+    pub vwap: f64,
+    #[serde(with = "string_or_float")]
+    pub quote_volume: f64,
+    /// Highest price, 0 without trades.
+    #[serde(rename = "highPrice", with = "string_or_float")]
+    pub high: f64,
+    /// Lowest price, 0 without trades.
+    #[serde(rename = "lowPrice", with = "string_or_float")]
+    pub low: f64,
+}
+
+#[derive(Deserialize)]
+struct SymbolStats {
+    symbol: String,
+    #[serde(flatten)]
+    stats: Stats,
+}
+
+/// The 24h statistics of every symbol. The request is heavy, so Binance
+/// may throttle it with [`RateLimited`].
+pub async fn stats_24h(credentials: Option<&Credentials>) -> Result<HashMap<String, Stats>> {
+    let _request = start_request(80);
+    match market(credentials)
+        .client
+        .get::<Vec<SymbolStats>>("/api/v3/ticker/24hr", None)
+        .await
+    {
+        Ok(all) => Ok(all.into_iter().map(|x| (x.symbol, x.stats)).collect()),
+        Err(e) => Err(report("Unable to get 24h stats", &e)),
+    }
+}
+
+/// The 24h statistics of the single pair `coin` + `quote`, to go with
+/// [`exact_pair`].
+pub async fn pair_stats(
+    coin: &str,
+    quote: &str,
+    credentials: Option<&Credentials>,
+) -> Result<Stats> {
+    let request = format!("symbol={}", format!("{coin}{quote}").to_uppercase());
+    let _request = start_request(2);
+    market(credentials)
+        .client
+        .get::<Stats>("/api/v3/ticker/24hr", Some(&request))
+        .await
+        .map_err(|e| report("Unable to get 24h stats", &e))
+}
+
+/// The subset of `/api/v3/exchangeInfo` needed for price precision.
+#[derive(Debug, Deserialize)]
+struct ExchangeInfo {
+    symbols: Vec<SymbolInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SymbolInfo {
+    symbol: String,
+    filters: Vec<SymbolFilter>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "filterType")]
+enum SymbolFilter {
+    #[serde(rename = "PRICE_FILTER", rename_all = "camelCase")]
+    Price { tick_size: String },
+    #[serde(other)]
+    Other,
+}
+
+/// Decimals needed to write prices on a `tick_size` grid such as
+/// `"0.01000000"`, or `None` when the tick size is not a positive number.
+pub fn tick_decimals(tick_size: &str) -> Option<usize> {
+    if !tick_size.parse::<f64>().is_ok_and(|tick| tick > 0.0) {
+        return None;
+    }
+    Some(
+        tick_size
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.trim_end_matches('0').len()),
+    )
+}
+
+/// Display decimals of every symbol, derived from its exchange tick size.
+pub async fn price_precisions(credentials: Option<&Credentials>) -> Result<HashMap<String, usize>> {
+    let _request = start_request(20);
+    match market(credentials)
+        .client
+        .get::<ExchangeInfo>("/api/v3/exchangeInfo", None)
+        .await
+    {
+        Ok(info) => Ok(info
+            .symbols
+            .into_iter()
+            .filter_map(|symbol| {
+                let decimals = symbol.filters.iter().find_map(|filter| match filter {
+                    SymbolFilter::Price { tick_size } => tick_decimals(tick_size),
+                    SymbolFilter::Other => None,
+                })?;
+                Some((symbol.symbol, decimals))
+            })
+            .collect()),
+        Err(e) => Err(report("Unable to get exchange info", &e)),
+    }
+}
+
+/// How far the Binance server clock is ahead of the local clock, in
+/// milliseconds, measured against the middle of the request.
+pub async fn server_time_offset() -> Result<i64> {
+    let general: binance::general::General = Binance::new(None, None);
+    let _request = start_request(1);
+    let before = local_millis();
+    let server = general
+        .get_server_time()
+        .await
+        .map_err(|e| report("Unable to get the server time", &e))?;
+    let after = local_millis();
+    Ok(i64::try_from(server.server_time).unwrap_or(i64::MAX) - (before + after) / 2)
+}
+
+fn local_millis() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    i64::try_from(now.as_millis()).unwrap_or(i64::MAX)
+}
+
+/// The symbols of an all-prices response. The binance crate has a single
+/// shape for it; matching here, in one place, means a shape added by a
+/// library update needs handling once, while a response Binance sends in
+/// some other shape fails to deserialize and is explained by
+/// [`describe_error`].
+fn all_prices(prices: Prices) -> Vec<SymbolPrice> {
+    match prices {
+        Prices::AllPrices(all) => all,
+    }
+}
+
+/// Every symbol currently listed on the exchange, for any quote asset.
+pub async fn list_symbols(credentials: Option<&Credentials>) -> Result<HashSet<String>> {
+    let _request = start_request(4);
+    match market(credentials).get_all_prices().await.map(all_prices) {
+        Ok(all_symbols) => Ok(all_symbols.into_iter().map(|x| x.symbol).collect()),
+        Err(e) => Err(report("Unable to list symbols", &e)),
+    }
+}
+
+/// Close price of `symbol`'s daily candle seven days ago.
+pub async fn week_ago_close(symbol: &str, credentials: Option<&Credentials>) -> Result<f64> {
+    let _request = start_request(2);
+    match market(credentials)
+        .get_klines(symbol, "1d", 8, None, None)
+        .await
+    {
+        Ok(binance::rest_model::KlineSummaries::AllKlineSummaries(klines)) => {
+            // the last candle is today's, so eight candles reach back a week
+            match klines.len().checked_sub(8).and_then(|i| klines.get(i)) {
+                Some(kline) => Ok(kline.close),
+                None => Err(color_eyre::eyre::eyre!(
+                    "{symbol} has less than a week of history"
+                )),
+            }
+        }
+        Err(e) => Err(report(&format!("Unable to get klines for {symbol}"), &e)),
+    }
+}
+
+/// Window of a price change: the 24h one the ticker reports, or a shorter
+/// one derived from klines.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Timeframe {
+    Hour,
+    FourHours,
+    #[default]
+    Day,
+}
+
+impl Timeframe {
+    /// The next window in the cycle 24h → 1h → 4h → 24h.
+    pub fn next(self) -> Self {
+        match self {
+            Timeframe::Day => Timeframe::Hour,
+            Timeframe::Hour => Timeframe::FourHours,
+            Timeframe::FourHours => Timeframe::Day,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Timeframe::Hour => "1h",
+            Timeframe::FourHours => "4h",
+            Timeframe::Day => "24h",
+        }
+    }
+
+    /// Kline interval, its length and the number of klines whose first one
+    /// opens at the start of the window; `None` for 24h.
+    fn klines(self) -> Option<(&'static str, Duration, u16)> {
+        match self {
+            Timeframe::Hour => Some(("1m", Duration::from_secs(60), 61)),
+            Timeframe::FourHours => Some(("5m", Duration::from_secs(5 * 60), 49)),
+            Timeframe::Day => None,
+        }
+    }
+
+    /// How long an open price from [`window_open`] holds: one kline, after
+    /// which the window has moved on.
+    pub fn refresh_after(self) -> Duration {
+        self.klines()
+            .map_or(Duration::ZERO, |(_, length, _)| length)
+    }
+}
+
+/// Price of `symbol` at the start of `timeframe`, to measure the change over
+/// windows shorter than the 24h one of [`MarketRow::change_pct`].
+pub async fn window_open(
+    symbol: &str,
+    timeframe: Timeframe,
+    credentials: Option<&Credentials>,
+) -> Result<f64> {
+    let Some((interval, _, limit)) = timeframe.klines() else {
+        return Err(color_eyre::eyre::eyre!(
+            "The 24h change comes with the ticker"
+        ));
+    };
+    let _request = start_request(2);
+    match market(credentials)
+        .get_klines(symbol, interval, limit, None, None)
+        .await
+    {
+        Ok(binance::rest_model::KlineSummaries::AllKlineSummaries(klines)) => {
+            // a younger pair starts at its first kline
+            match klines.first() {
+                Some(kline) => Ok(kline.open),
+                None => Err(color_eyre::eyre::eyre!("{symbol} has no klines")),
+            }
+        }
+        Err(e) => Err(report(&format!("Unable to get klines for {symbol}"), &e)),
+    }
+}
+
+/// Symbols quoted in `quote` whose base starts with `coin`, compared
+/// case-insensitively. An empty or missing `coin` keeps every pair of `quote`.
+/// Each symbol appears once, even if the exchange listed it twice.
+pub fn filter_symbols(all: &[SymbolPrice], coin: Option<&str>, quote: &str) -> Vec<SymbolPrice> {
+    let coin = coin.map(str::to_uppercase).unwrap_or_default();
+    let quote = quote.to_uppercase();
+    let any_quote = quote == ALL_QUOTES;
+    let pairs = all.iter().filter(|x| {
+        x.symbol.len() > quote.len()
+            && x.symbol.starts_with(&coin)
+            && (any_quote || x.symbol.ends_with(&quote))
+    });
+    // a symbol listed twice keeps its first place but the later price
+    let mut index = HashMap::new();
+    let mut unique: Vec<SymbolPrice> = Vec::new();
+    for pair in pairs {
+        match index.get(&pair.symbol) {
+            Some(&i) => unique[i] = pair.clone(),
+            None => {
+                index.insert(pair.symbol.clone(), unique.len());
+                unique.push(pair.clone());
+            }
+        }
+    }
+    unique
+}
+
+/// Returned (inside the `eyre` report) when a searched coin has no pair with
+/// the quote asset, telling apart an unknown coin from one that only
+/// trades against other quotes.
+#[derive(Debug, PartialEq)]
+pub struct NoPairs {
+    pub coin: String,
+    pub quote: String,
+    /// Symbols starting with the coin for any quote; empty when the coin is
+    /// not listed at all.
+    pub other_pairs: Vec<String>,
+}
+
+impl fmt::Display for NoPairs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.other_pairs.is_empty() {
+            return write!(f, "No coin matching {} is listed on Binance", self.coin);
+        }
+        const SHOWN: usize = 5;
+        write!(
+            f,
+            "{} has no {} pair; it trades as {}",
+            self.coin,
+            self.quote,
+            self.other_pairs[..self.other_pairs.len().min(SHOWN)].join(", ")
+        )?;
+        if self.other_pairs.len() > SHOWN {
+            write!(f, " and {} more", self.other_pairs.len() - SHOWN)?;
+        }
+        write!(f, ". Try another quote asset.")
+    }
+}
+
+impl std::error::Error for NoPairs {}
+
+/// [`filter_symbols`], failing with [`NoPairs`] when a `coin` was given but
+/// nothing matched.
+pub fn find_pairs(
+    all: &[SymbolPrice],
+    coin: Option<&str>,
+    quote: &str,
+) -> std::result::Result<Vec<SymbolPrice>, NoPairs> {
+    let pairs = filter_symbols(all, coin, quote);
+    match coin.map(str::to_uppercase) {
+        Some(coin) if pairs.is_empty() && !coin.is_empty() => Err(NoPairs {
+            other_pairs: all
+                .iter()
+                .filter(|x| x.symbol.starts_with(&coin))
+                .map(|x| x.symbol.clone())
+                .collect(),
+            coin,
+            quote: quote.to_uppercase(),
+        }),
+        _ => Ok(pairs),
+    }
+}
+
+/// Fetch the latest price and 24h change of every `quote` pair, keeping only
+/// symbols starting with `coin` when given.
+pub async fn market_data(
+    coin: Option<&str>,
+    quote: &str,
+    credentials: Option<&Credentials>,
+) -> Result<Vec<MarketRow>> {
+    // Latest price and 24h change for ALL symbols
+    let (rows, stats) = futures::join!(
+        market_prices(coin, quote, credentials),
+        stats_24h(credentials)
+    );
+    let mut rows = rows?;
+    // the change column is optional, so a failed stats request only blanks it
+    let stats = stats.unwrap_or_else(|e| {
+        log::warn!("{e}");
+        HashMap::new()
+    });
+    for row in &mut rows {
+        row.set_stats(stats.get(&row.symbol));
+    }
+    Ok(rows)
+}
+
+/// Prices of `base` against each of `quotes` that has a pair, in the order
+/// of `quotes`.
+pub fn base_prices<'a>(
+    prices: impl IntoIterator<Item = (&'a str, f64)>,
+    base: &str,
+    quotes: &[String],
+) -> Vec<(String, f64)> {
+    let base = base.to_uppercase();
+    let by_symbol = prices.into_iter().collect::<HashMap<_, _>>();
+    quotes
+        .iter()
+        .filter_map(|quote| {
+            let quote = quote.to_uppercase();
+            let price = by_symbol.get(format!("{base}{quote}").as_str())?;
+            Some((quote, *price))
+        })
+        .collect()
+}
+
+/// Fetch the prices of `base` against each of `quotes`, see [`base_prices`].
+pub async fn pair_prices(
+    base: &str,
+    quotes: &[String],
+    credentials: Option<&Credentials>,
+) -> Result<Vec<(String, f64)>> {
+    let _request = start_request(4);
+    match market(credentials).get_all_prices().await.map(all_prices) {
+        Ok(all_symbols) => Ok(base_prices(
+            all_symbols.iter().map(|x| (x.symbol.as_str(), x.price)),
+            base,
+            quotes,
+        )),
+        Err(e) => Err(report("Unable to get prices", &e)),
+    }
+}
+
+/// Fetch the price of the single pair `coin` + `quote` by its exact symbol.
+/// Much smaller than [`market_data`] on slow connections. `Ok(None)` when
+/// Binance does not list that symbol, so the caller can fall back to a
+/// prefix search.
+pub async fn exact_pair(
+    coin: &str,
+    quote: &str,
+    credentials: Option<&Credentials>,
+) -> Result<Option<MarketRow>> {
+    let symbol = format!("{coin}{quote}").to_uppercase();
+    let _request = start_request(2);
+    match market(credentials).get_price(&symbol).await {
+        Ok(price) => Ok(Some(MarketRow {
+            symbol: price.symbol,
+            price: price.price,
+            change_pct: None,
+            change_7d: None,
+            vwap: None,
+            quote_volume: None,
+            high: None,
+            low: None,
+        })),
+        // -1121 invalid symbol
+        Err(binance::errors::Error::BinanceError { response }) if response.code == -1121 => {
+            Ok(None)
+        }
+        Err(e) => Err(report("Unable to get market data", &e)),
+    }
+}
+
+/// Fetch only the latest price of every `quote` pair, keeping only symbols
+/// starting with `coin` when given. Cheaper than [`market_data`] but leaves
+/// the 24h statistics empty.
+pub async fn market_prices(
+    coin: Option<&str>,
+    quote: &str,
+    credentials: Option<&Credentials>,
+) -> Result<Vec<MarketRow>> {
+    let _request = start_request(4);
+    match market(credentials).get_all_prices().await.map(all_prices) {
+        Ok(all_symbols) => Ok(find_pairs(&all_symbols, coin, quote)?
+            .into_iter()
+            .map(|x| MarketRow {
+                symbol: x.symbol,
+                price: x.price,
+                change_pct: None,
+                change_7d: None,
+                vwap: None,
+                quote_volume: None,
+                high: None,
+                low: None,
+            })
+            .collect()),
+        Err(e) => Err(report("Unable to get market prices", &e)),
+    }
+}
+
+/// Turn a Binance client error into a message that tells the user what to do.
+pub fn describe_error(e: &binance::errors::Error) -> String {
+    use binance::errors::Error;
+    match e {
+        Error::ReqError(e) if e.is_connect() => {
+            format!("cannot connect to Binance, check your network and DNS ({e})")
+        }
+        Error::ReqError(e) if e.is_timeout() => {
+            "the request to Binance timed out, the network may be slow or offline".to_owned()
+        }
+        Error::Msg(msg) if matches!(response_status(e), Some(451 | 403)) => {
+            format!("Binance refused the request, it may be unavailable in your region ({msg})")
+        }
+        Error::Msg(msg) if matches!(response_status(e), Some(429 | 418)) => {
+            format!("rate limited by Binance, try again later ({msg})")
+        }
+        _ if is_unexpected_format(e) => {
+            format!("Binance answered in an unexpected format, the API may have changed ({e})")
+        }
+        Error::ServiceUnavailable | Error::InternalServerError => {
+            format!("Binance is having problems ({e}), try again later")
+        }
+        e => e.to_string(),
+    }
+}
+
+/// Whether `e` is a response that did not deserialize into the expected model.
+fn is_unexpected_format(e: &binance::errors::Error) -> bool {
+    match e {
+        binance::errors::Error::Json(_) => true,
+        binance::errors::Error::ReqError(e) => e.is_decode(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prices(symbols: &[&str]) -> Vec<SymbolPrice> {
+        symbols
+            .iter()
+            .map(|symbol| SymbolPrice {
+                symbol: (*symbol).to_owned(),
+                price: 1.0,
+            })
+            .collect()
+    }
+
+    fn symbols(prices: &[SymbolPrice]) -> Vec<&str> {
+        prices.iter().map(|x| x.symbol.as_str()).collect()
+    }
+
+    const ALL: &[&str] = &[
+        "BTCUSDT",
+        "BTCBUSD",
+        "ETHUSDT",
+        "ETHBTC",
+        "BTCDOMUSDT",
+        "USDT",
+    ];
+
+    #[test]
+    fn duplicate_symbols_keep_the_later_price() {
+        let mut all = prices(&["BTCUSDT", "ETHUSDT"]);
+        all.push(SymbolPrice {
+            symbol: "BTCUSDT".to_owned(),
+            price: 2.0,
+        });
+        let pairs = filter_symbols(&all, None, "USDT");
+        assert_eq!(symbols(&pairs), ["BTCUSDT", "ETHUSDT"]);
+        assert_eq!(pairs[0].price, 2.0);
+    }
+
+    #[test]
+    fn no_query_keeps_every_pair_of_the_quote() {
+        let all = prices(ALL);
+        assert_eq!(
+            symbols(&filter_symbols(&all, None, "USDT")),
+            ["BTCUSDT", "ETHUSDT", "BTCDOMUSDT"]
+        );
+        assert_eq!(
+            filter_symbols(&all, Some(""), "USDT"),
+            filter_symbols(&all, None, "USDT")
+        );
+    }
+
+    #[test]
+    fn query_matches_base_prefix() {
+        let all = prices(ALL);
+        assert_eq!(
+            symbols(&filter_symbols(&all, Some("BTC"), "USDT")),
+            ["BTCUSDT", "BTCDOMUSDT"]
+        );
+        assert!(filter_symbols(&all, Some("TC"), "USDT").is_empty());
+    }
+
+    #[test]
+    fn quote_is_matched_as_suffix() {
+        let all = prices(ALL);
+        assert_eq!(symbols(&filter_symbols(&all, None, "BTC")), ["ETHBTC"]);
+        assert_eq!(
+            symbols(&filter_symbols(&all, Some("BTC"), "BUSD")),
+            ["BTCBUSD"]
+        );
+    }
+
+    #[test]
+    fn every_quote_keeps_all_pairs_and_detects_their_quote() {
+        let all = prices(ALL);
+        assert_eq!(
+            symbols(&filter_symbols(&all, Some("BTC"), ALL_QUOTES)),
+            ["BTCUSDT", "BTCBUSD", "BTCDOMUSDT"]
+        );
+        assert_eq!(quote_of("ETHBTC"), Some("BTC"));
+        assert_eq!(quote_of("BTCFDUSD"), Some("FDUSD"));
+        assert_eq!(quote_of("USDT"), None);
+        assert_eq!(quote_of("BTCXYZ"), None);
+    }
+
+    #[test]
+    fn missing_pairs_are_explained() {
+        let all = prices(ALL);
+        let err = find_pairs(&all, Some("eth"), "BUSD").unwrap_err();
+        assert_eq!(err.other_pairs, ["ETHUSDT", "ETHBTC"]);
+        assert_eq!(
+            err.to_string(),
+            "ETH has no BUSD pair; it trades as ETHUSDT, ETHBTC. Try another quote asset."
+        );
+        let err = find_pairs(&all, Some("XYZ"), "USDT").unwrap_err();
+        assert!(err.other_pairs.is_empty());
+        assert!(find_pairs(&all, None, "EUR").unwrap().is_empty());
+    }
+
+    #[test]
+    fn price_responses_of_another_shape_are_explained() {
+        let list = r#"[{"symbol": "BTCUSDT", "price": "65000.5"}]"#;
+        let prices = serde_json::from_str::<Prices>(list).unwrap();
+        assert_eq!(symbols(&all_prices(prices)), ["BTCUSDT"]);
+
+        // the shape of a single symbol request
+        let single = r#"{"symbol": "BTCUSDT", "price": "65000.5"}"#;
+        let e = serde_json::from_str::<Prices>(single).unwrap_err();
+        assert!(describe_error(&binance::errors::Error::Json(e))
+            .starts_with("Binance answered in an unexpected format"));
+    }
+
+    #[test]
+    fn throttling_is_told_by_the_status_alone() {
+        use binance::errors::Error;
+        let limited = report("x", &Error::Msg("Received response: 429".to_owned()));
+        assert!(limited.downcast_ref::<RateLimited>().is_some());
+        let banned = report("x", &Error::Msg("Received response: 418".to_owned()));
+        assert!(banned.downcast_ref::<RateLimited>().is_some());
+        let other = report(
+            "x",
+            &Error::Msg("Received response: 404 for 4290USDT".to_owned()),
+        );
+        assert!(other.downcast_ref::<RateLimited>().is_none());
+    }
+
+    #[test]
+    fn base_prices_follow_the_quote_order() {
+        let all = prices(ALL);
+        let quotes = ["busd", "USDC", "USDT"].map(str::to_owned);
+        assert_eq!(
+            base_prices(
+                all.iter().map(|x| (x.symbol.as_str(), x.price)),
+                "btc",
+                &quotes
+            ),
+            [("BUSD".to_owned(), 1.0), ("USDT".to_owned(), 1.0)]
+        );
+    }
+
+    #[test]
+    fn stats_are_read_from_the_ticker() {
+        let json = r#"[{"symbol":"BTCUSDT","priceChange":"-94.99","priceChangePercent":"-0.095",
+            "weightedAvgPrice":"100.5","highPrice":"101","lowPrice":"99.5","quoteVolume":"1234.5",
+            "count":76}]"#;
+        let all = serde_json::from_str::<Vec<SymbolStats>>(json).unwrap();
+        assert_eq!(all[0].symbol, "BTCUSDT");
+        assert_eq!(
+            all[0].stats,
+            Stats {
+                change_pct: -0.095,
+                vwap: 100.5,
+                quote_volume: 1234.5,
+                high: 101.0,
+                low: 99.5,
+            }
+        );
+    }
+
+    #[test]
+    fn tick_size_gives_decimals() {
+        assert_eq!(tick_decimals("0.01000000"), Some(2));
+        assert_eq!(tick_decimals("0.00000001"), Some(8));
+        assert_eq!(tick_decimals("1.00000000"), Some(0));
+        assert_eq!(tick_decimals("10"), Some(0));
+        assert_eq!(tick_decimals("0.00000000"), None);
+    }
+
+    #[test]
+    fn matching_ignores_case() {
+        let all = prices(ALL);
+        assert_eq!(
+            filter_symbols(&all, Some("eth"), "usdt"),
+            filter_symbols(&all, Some("ETH"), "USDT")
+        );
+    }
+}
@@ -0,0 +1,72 @@
+use std::time::{Duration, Instant};
+
+/// Ticks between two logged summaries.
+const SUMMARY_TICKS: u32 = 10;
+
+/// Count, total and worst duration of one kind of work.
+#[derive(Debug, Default)]
+struct Timings {
+    count: u32,
+    total: Duration,
+    max: Duration,
+}
+
+impl Timings {
+    fn record(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.total += elapsed;
+        self.max = self.max.max(elapsed);
+    }
+
+    fn summary(&self) -> String {
+        if self.count == 0 {
+            return "none".to_owned();
+        }
+        format!(
+            "{} avg {:.1?} max {:.1?}",
+            self.count,
+            self.total / self.count,
+            self.max
+        )
+    }
+}
+
+/// Timings logged every [`SUMMARY_TICKS`] ticks with `--debug-metrics`, to
+/// make performance reports actionable.
+#[derive(Debug, Default)]
+pub struct Metrics {
+    renders: Timings,
+    fetches: Timings,
+    updates: Timings,
+    ticks: u32,
+}
+
+impl Metrics {
+    pub fn record_render(&mut self, started: Instant) {
+        self.renders.record(started.elapsed());
+    }
+
+    pub fn record_fetch(&mut self, started: Instant) {
+        self.fetches.record(started.elapsed());
+    }
+
+    pub fn record_update(&mut self, started: Instant) {
+        self.updates.record(started.elapsed());
+    }
+
+    /// Count a tick, logging and resetting the timings every
+    /// [`SUMMARY_TICKS`] ticks.
+    pub fn tick(&mut self, rows: usize) {
+        self.ticks += 1;
+        if self.ticks < SUMMARY_TICKS {
+            return;
+        }
+        log::info!(
+            "metrics: renders {} | fetches {} | updates {} | {rows} rows",
+            self.renders.summary(),
+            self.fetches.summary(),
+            self.updates.summary(),
+        );
+        *self = Self::default();
+    }
+}
@@ -0,0 +1,110 @@
+use tui_textarea::TextArea;
+
+use crate::Action;
+
+/// Actions listed by the command palette, with the key bound to each.
+pub const COMMANDS: &[(&str, Action)] = &[
+    ("Search coin (s)", Action::SearchFocus),
+    ("Refresh prices (r)", Action::RefreshPrices),
+    ("Full refresh (R)", Action::Refresh),
+    ("Toggle auto-refresh (a)", Action::ToggleAutoRefresh),
+    ("Toggle ticker mode (A)", Action::ToggleTicker),
+    ("Pause or resume (p)", Action::TogglePause),
+    ("Toggle chart (c)", Action::ToggleChart),
+    ("Toggle update times (t)", Action::ToggleUpdated),
+    ("Show moved only (m)", Action::ToggleMoved),
+    ("Cycle view (V)", Action::CycleView),
+    ("Reset view (0)", Action::ResetView),
+    ("Stablecoin view (g)", Action::OpenStableView),
+    ("Compare two side by side (v)", Action::PickForPair),
+    ("Next favorite (])", Action::JumpToFavorite(1)),
+    ("Previous favorite ([)", Action::JumpToFavorite(-1)),
+    ("Toggle favorite (f)", Action::ToggleFavorite),
+    ("Favorites digest (D)", Action::OpenDigest),
+    ("Price alerts (B)", Action::OpenAlerts),
+    ("Edit note (n)", Action::EditNote),
+    ("Mark row (space)", Action::ToggleMark),
+    ("Unmark all (u)", Action::ClearMarks),
+    ("Cycle sort (S)", Action::CycleSort),
+    ("Sort by volume (b)", Action::ToggleVolumeSort),
+    ("Flip sort direction (d)", Action::ToggleSortDir),
+    ("Toggle 7d change (w)", Action::Toggle7d),
+    ("Change window (T)", Action::CycleTimeframe),
+    ("Scientific notation (E)", Action::ToggleNotation),
+    ("Inverse prices (i)", Action::ToggleInverse),
+    ("Next color (→)", Action::NextColor),
+    ("Previous color (←)", Action::PreviousColor),
+    ("Export (e)", Action::Export),
+    ("Copy (y)", Action::Copy),
+    ("Copy table (Y)", Action::CopyTable),
+    ("Copy as JSON (J)", Action::CopyJson),
+    ("Open in browser (o)", Action::OpenInBrowser),
+    ("Dismiss message (x)", Action::DismissFlash),
+    ("Reset API usage (U)", Action::ResetApiUsage),
+    ("Settings (,)", Action::OpenSettings),
+    ("Log (L)", Action::ToggleLog),
+    ("Quit (q)", Action::Quit),
+];
+
+/// State of the open command palette.
+pub struct Palette<'a> {
+    pub input: TextArea<'a>,
+    /// Index into [`Palette::matches`].
+    pub selected: usize,
+}
+
+impl<'a> Palette<'a> {
+    pub fn new(input: TextArea<'a>) -> Self {
+        Self { input, selected: 0 }
+    }
+
+    /// Commands matching the typed query, in [`COMMANDS`] order.
+    pub fn matches(&self) -> Vec<&'static (&'static str, Action)> {
+        let query = self.input.lines().join("");
+        COMMANDS
+            .iter()
+            .filter(|(label, _)| fuzzy_match(label, &query))
+            .collect()
+    }
+
+    /// The selected command's action, if any command matches.
+    pub fn chosen(&self) -> Option<Action> {
+        self.matches()
+            .get(self.selected)
+            .map(|(_, action)| action.clone())
+    }
+
+    /// Move the selection by `delta` within the matches, wrapping around.
+    pub fn move_by(&mut self, delta: isize) {
+        let count = self.matches().len();
+        if count > 0 {
+            self.selected = (self.selected as isize + delta).rem_euclid(count as isize) as usize;
+        }
+    }
+}
+
+/// Whether the characters of `query` appear in `label` in order, ignoring
+/// case and spaces, so "tgch" finds "Toggle chart".
+pub fn fuzzy_match(label: &str, query: &str) -> bool {
+    let mut label = label.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .all(|c| label.any(|l| l == c))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_match_as_ordered_subsequences() {
+        assert!(fuzzy_match("Toggle chart (c)", "tgch"));
+        assert!(fuzzy_match("Toggle chart (c)", "CHART"));
+        assert!(fuzzy_match("Toggle chart (c)", "toggle c"));
+        assert!(fuzzy_match("Toggle chart (c)", ""));
+        assert!(!fuzzy_match("Toggle chart (c)", "chartt"));
+        assert!(!fuzzy_match("Toggle chart (c)", "ct g"));
+    }
+}
@@ -0,0 +1,86 @@
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use coinmarket::MarketRow;
+use color_eyre::eyre::{Result, WrapErr};
+use hyper::{
+    header,
+    service::{make_service_fn, service_fn},
+    Body, Method, Request, Response, Server, StatusCode,
+};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+
+/// The rows the table shows, after search, filters and sort; the TUI
+/// replaces them whenever they change.
+#[derive(Clone, Debug, Default)]
+pub struct SharedRows(Arc<Mutex<Vec<MarketRow>>>);
+
+impl SharedRows {
+    pub fn set(&self, rows: &[MarketRow]) {
+        let mut shared = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        *shared = rows.to_vec();
+    }
+
+    fn to_json(&self) -> serde_json::Result<String> {
+        let rows = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        serde_json::to_string(&*rows)
+    }
+}
+
+/// Serve `rows` as JSON on `GET /` at `addr` until `shutdown` is cancelled.
+/// Binding happens before returning, so a taken port fails the start.
+pub fn serve(
+    addr: SocketAddr,
+    rows: SharedRows,
+    shutdown: CancellationToken,
+) -> Result<JoinHandle<()>> {
+    let builder = Server::try_bind(&addr).wrap_err_with(|| format!("binding {addr}"))?;
+    let make_service = make_service_fn(move |_| {
+        let rows = rows.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |request| {
+                let rows = rows.clone();
+                async move { Ok::<_, Infallible>(respond(&request, &rows)) }
+            }))
+        }
+    });
+    let server = builder
+        .serve(make_service)
+        .with_graceful_shutdown(async move { shutdown.cancelled().await });
+    log::info!("Serving the table on http://{addr}/");
+    Ok(tokio::spawn(async move {
+        if let Err(e) = server.await {
+            log::error!("The server stopped: {e}");
+        }
+    }))
+}
+
+fn respond(request: &Request<Body>, rows: &SharedRows) -> Response<Body> {
+    let status = |status: StatusCode| {
+        let mut response = Response::new(Body::from(status.to_string()));
+        *response.status_mut() = status;
+        response
+    };
+    match (request.method(), request.uri().path()) {
+        (&Method::GET, "/") => match rows.to_json() {
+            Ok(json) => {
+                let mut response = Response::new(Body::from(json));
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    header::HeaderValue::from_static("application/json"),
+                );
+                response
+            }
+            Err(e) => {
+                log::error!("Serializing the rows failed: {e}");
+                status(StatusCode::INTERNAL_SERVER_ERROR)
+            }
+        },
+        (_, "/") => status(StatusCode::METHOD_NOT_ALLOWED),
+        _ => status(StatusCode::NOT_FOUND),
+    }
+}
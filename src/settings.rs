@@ -0,0 +1,59 @@
+use tui_textarea::TextArea;
+
+/// A setting listed on the settings screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field {
+    QuoteAsset,
+    RefreshInterval,
+    Precision,
+    Theme,
+}
+
+impl Field {
+    pub const ALL: [Field; 4] = [
+        Field::QuoteAsset,
+        Field::RefreshInterval,
+        Field::Precision,
+        Field::Theme,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Field::QuoteAsset => "Quote asset",
+            Field::RefreshInterval => "Refresh interval (s)",
+            Field::Precision => "Price decimals",
+            Field::Theme => "Theme",
+        }
+    }
+
+    /// Whether Enter edits the value as text; the theme is cycled instead.
+    pub fn is_text(self) -> bool {
+        self != Field::Theme
+    }
+}
+
+/// State of the open settings screen.
+#[derive(Default)]
+pub struct Settings<'a> {
+    /// Index into [`Field::ALL`].
+    pub selected: usize,
+    /// Editor for the selected field while it is being edited.
+    pub editor: Option<TextArea<'a>>,
+    /// Why the last edit could not be applied.
+    pub error: Option<String>,
+    /// Whether anything changed, so the config needs saving on close.
+    pub changed: bool,
+}
+
+impl<'a> Settings<'a> {
+    pub fn field(&self) -> Field {
+        Field::ALL[self.selected]
+    }
+
+    /// Move the selection by `delta`, wrapping around.
+    pub fn move_by(&mut self, delta: isize) {
+        let count = Field::ALL.len();
+        self.selected = (self.selected as isize + delta).rem_euclid(count as isize) as usize;
+        self.error = None;
+    }
+}
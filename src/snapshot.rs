@@ -0,0 +1,54 @@
+use std::{
+    fs,
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use coinmarket::MarketRow;
+use color_eyre::eyre::{Result, WrapErr};
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Market rows saved at a point in time, for comparing later.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Unix time in seconds when the rows were fetched.
+    pub taken_at: u64,
+    pub rows: Vec<MarketRow>,
+}
+
+impl Snapshot {
+    pub fn new(rows: Vec<MarketRow>) -> Self {
+        let taken_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        Self { taken_at, rows }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)? + "\n";
+        fs::write(path, contents).wrap_err_with(|| format!("writing {}", path.display()))
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents =
+            fs::read_to_string(path).wrap_err_with(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&contents).wrap_err_with(|| format!("parsing {}", path.display()))
+    }
+}
+
+/// Fetch every pair of the configured quote asset and save it to `path`.
+pub async fn take(config: &Config, path: &Path) -> Result<()> {
+    let quote = config
+        .quote_asset
+        .as_deref()
+        .unwrap_or(crate::DEFAULT_QUOTE_ASSET);
+    let rows =
+        coinmarket::market_data(None, &quote.to_uppercase(), config.credentials().as_ref()).await?;
+    let count = rows.len();
+    Snapshot::new(rows).save(path)?;
+    println!("Saved {count} prices to {}", path.display());
+    Ok(())
+}
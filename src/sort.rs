@@ -0,0 +1,77 @@
+use std::cmp::Ordering;
+
+use crate::MarketRow;
+
+/// Column the table is sorted by; `None` keeps the exchange's order.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortMode {
+    #[default]
+    None,
+    Symbol,
+    Price,
+    Change,
+    /// 24h quote volume, a stand-in for market cap since Binance does not
+    /// report circulating supply.
+    Volume,
+}
+
+impl SortMode {
+    /// The next mode in the cycle None → Symbol → Price → Change → Volume
+    /// → None.
+    pub fn next(self) -> Self {
+        match self {
+            SortMode::None => SortMode::Symbol,
+            SortMode::Symbol => SortMode::Price,
+            SortMode::Price => SortMode::Change,
+            SortMode::Change => SortMode::Volume,
+            SortMode::Volume => SortMode::None,
+        }
+    }
+
+    /// Direction a column starts in when selected: names A→Z, numbers high→low.
+    pub fn default_descending(self) -> bool {
+        !matches!(self, SortMode::None | SortMode::Symbol)
+    }
+}
+
+/// Sort `rows` in place by `mode`. Rows without a 24h change or volume always
+/// sort last, and rows that tie are ordered by symbol A→Z in either
+/// direction, so a refresh does not shuffle them.
+pub fn sort_market_data(rows: &mut [MarketRow], mode: SortMode, descending: bool) {
+    let directed = |ordering: Ordering| {
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    };
+    match mode {
+        SortMode::None => {}
+        SortMode::Symbol => rows.sort_by(|a, b| directed(a.symbol.cmp(&b.symbol))),
+        SortMode::Price => rows.sort_by(|a, b| {
+            directed(a.price.total_cmp(&b.price)).then_with(|| a.symbol.cmp(&b.symbol))
+        }),
+        SortMode::Change => sort_by_optional(rows, descending, |row| row.change_pct),
+        SortMode::Volume => sort_by_optional(rows, descending, |row| row.quote_volume),
+    }
+}
+
+/// Sort `rows` in place by the value `key` gives for each, e.g. the change
+/// over a window other than 24h. Rows without one always sort last; ties are
+/// ordered by symbol.
+pub fn sort_by_optional(
+    rows: &mut [MarketRow],
+    descending: bool,
+    key: impl Fn(&MarketRow) -> Option<f64>,
+) {
+    rows.sort_by(|a, b| {
+        match (key(a), key(b)) {
+            (Some(a), Some(b)) if descending => b.total_cmp(&a),
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+        .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+}
@@ -0,0 +1,128 @@
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// Delay before the first restart of a panicked task, doubled per restart.
+const FIRST_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+/// Restarts of one task before it is left down.
+const MAX_RESTARTS: u32 = 5;
+
+tokio::task_local! {
+    /// Name of the supervised task being polled.
+    static TASK: &'static str;
+}
+
+/// Name of the supervised task the caller runs in, if any; the panic hook
+/// uses it to leave the terminal alone for panics the supervisor handles.
+pub fn current_task() -> Option<&'static str> {
+    TASK.try_with(|name| *name).ok()
+}
+
+/// Runs background tasks, restarting those that panic with a growing delay
+/// and keeping count of the ones that are down.
+#[derive(Clone, Debug, Default)]
+pub struct Supervisor(Arc<Mutex<BTreeMap<&'static str, usize>>>);
+
+impl Supervisor {
+    /// Run the future made by `task` in the background. A panic is logged
+    /// and the task started again, up to [`MAX_RESTARTS`] times; a task that
+    /// returns is done.
+    pub fn spawn<F, Fut>(&self, name: &'static str, task: F)
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let supervisor = self.clone();
+        tokio::spawn(async move {
+            let mut backoff = FIRST_BACKOFF;
+            let mut restarts = 0;
+            loop {
+                let result = tokio::spawn(TASK.scope(name, task())).await;
+                match result {
+                    Ok(()) => return,
+                    Err(e) if e.is_cancelled() => return,
+                    Err(_) if restarts == MAX_RESTARTS => {
+                        log::error!("Background task {name} keeps failing, giving up");
+                        supervisor.set_down(name, true);
+                        return;
+                    }
+                    Err(_) => {
+                        log::warn!(
+                            "Restarting background task {name} in {}s",
+                            backoff.as_secs()
+                        );
+                        supervisor.set_down(name, true);
+                        restarts += 1;
+                        tokio::time::sleep(backoff).await;
+                        // down only while waiting, not while it runs again
+                        supervisor.set_down(name, false);
+                        backoff = (backoff * 2).min(MAX_BACKOFF);
+                    }
+                }
+            }
+        });
+    }
+
+    fn set_down(&self, name: &'static str, down: bool) {
+        let mut tasks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        let count = tasks.entry(name).or_default();
+        if down {
+            *count += 1;
+        } else {
+            *count = count.saturating_sub(1);
+        }
+        if *count == 0 {
+            tasks.remove(name);
+        }
+    }
+
+    /// Names of the tasks that panicked and are not running again yet.
+    pub fn down(&self) -> Vec<&'static str> {
+        let tasks = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        tasks.keys().copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn a_restarted_task_is_not_down() {
+        let supervisor = Supervisor::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let counted = runs.clone();
+        supervisor.spawn("flaky", move || {
+            let runs = counted.clone();
+            async move {
+                if runs.fetch_add(1, Ordering::SeqCst) == 0 {
+                    panic!("first run fails");
+                }
+                std::future::pending::<()>().await;
+            }
+        });
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while supervisor.down().is_empty() {
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .expect("the panic was not noticed");
+        assert_eq!(supervisor.down(), ["flaky"]);
+
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while runs.load(Ordering::SeqCst) < 2 {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("the task was not restarted");
+        assert!(supervisor.down().is_empty());
+    }
+}
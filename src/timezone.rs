@@ -0,0 +1,92 @@
+use chrono::{DateTime, FixedOffset, Local, Utc};
+
+/// Time zone of the shown timestamps: the system's, or a fixed offset from
+/// UTC. Named zones such as `Europe/Berlin` need a zone database that is
+/// not bundled, so they are not supported.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum TimeZone {
+    #[default]
+    Local,
+    Fixed(FixedOffset),
+}
+
+impl TimeZone {
+    /// Parse `local`, `UTC` or an offset such as `+05:30`, `-0800` or
+    /// `UTC+2`, ignoring case.
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_uppercase();
+        if name == "LOCAL" {
+            return Some(TimeZone::Local);
+        }
+        let offset = name
+            .strip_prefix("UTC")
+            .or_else(|| name.strip_prefix("GMT"))
+            .unwrap_or(&name);
+        if offset.is_empty() || offset == "Z" {
+            return FixedOffset::east_opt(0).map(TimeZone::Fixed);
+        }
+        let (sign, digits) = if let Some(digits) = offset.strip_prefix('+') {
+            (1, digits)
+        } else if let Some(digits) = offset.strip_prefix('-') {
+            (-1, digits)
+        } else {
+            return None;
+        };
+        // ASCII only, so the byte offsets split below are char boundaries
+        if !digits.chars().all(|c| c.is_ascii_digit() || c == ':') {
+            return None;
+        }
+        let (hours, minutes) = match digits.split_once(':') {
+            Some((hours, minutes)) => (hours, minutes),
+            None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+            None => (digits, "0"),
+        };
+        let hours = hours.parse::<i32>().ok().filter(|_| hours.len() <= 2)?;
+        let minutes = minutes.parse::<i32>().ok().filter(|m| *m < 60)?;
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60)).map(TimeZone::Fixed)
+    }
+
+    /// The configured `timezone`, warning about and ignoring invalid ones.
+    pub fn resolve(name: Option<&str>) -> Self {
+        let Some(name) = name else {
+            return TimeZone::Local;
+        };
+        Self::parse(name).unwrap_or_else(|| {
+            log::warn!("Unknown timezone {name:?}, using local time");
+            TimeZone::Local
+        })
+    }
+
+    /// `time` in this zone, formatted with the `chrono` format string `format`.
+    pub fn format_time<Tz: chrono::TimeZone>(self, time: &DateTime<Tz>, format: &str) -> String {
+        match self {
+            TimeZone::Local => time.with_timezone(&Local).format(format).to_string(),
+            TimeZone::Fixed(offset) => time.with_timezone(&offset).format(format).to_string(),
+        }
+    }
+
+    /// The current time formatted like [`TimeZone::format_time`].
+    pub fn now(self, format: &str) -> String {
+        self.format_time(&Utc::now(), format)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_parse_in_several_spellings() {
+        let offset = |secs| Some(TimeZone::Fixed(FixedOffset::east_opt(secs).unwrap()));
+        assert_eq!(TimeZone::parse("Local"), Some(TimeZone::Local));
+        assert_eq!(TimeZone::parse("utc"), offset(0));
+        assert_eq!(TimeZone::parse("+05:30"), offset(5 * 3600 + 1800));
+        assert_eq!(TimeZone::parse("-0800"), offset(-8 * 3600));
+        assert_eq!(TimeZone::parse("UTC+2"), offset(2 * 3600));
+        assert_eq!(TimeZone::parse("Europe/Berlin"), None);
+        assert_eq!(TimeZone::parse("+25"), None);
+        assert_eq!(TimeZone::parse("UTC€1"), None);
+        assert_eq!(TimeZone::parse("é"), None);
+        assert_eq!(TimeZone::parse("+1€"), None);
+    }
+}
@@ -7,10 +7,11 @@ use color_eyre::eyre::Result;
 use crossterm::{
     cursor,
     event::{
-        DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste, EnableMouseCapture,
-        Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent,
+        DisableBracketedPaste, DisableFocusChange, DisableMouseCapture, EnableBracketedPaste,
+        EnableFocusChange, EnableMouseCapture, Event as CrosstermEvent, KeyEvent, KeyEventKind,
+        MouseEvent,
     },
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures::{FutureExt, StreamExt};
 use ratatui::backend::CrosstermBackend as Backend;
@@ -49,6 +50,41 @@ impl From<Event> for Input {
     }
 }
 
+/// OS signals (SIGTERM, SIGINT, SIGHUP) that quit the app the same way `q`
+/// does, so the terminal is restored instead of left in raw mode.
+pub struct ShutdownSignals {
+    #[cfg(unix)]
+    signals: Vec<tokio::signal::unix::Signal>,
+}
+
+impl ShutdownSignals {
+    pub fn new() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            let signals = [
+                SignalKind::terminate(),
+                SignalKind::interrupt(),
+                SignalKind::hangup(),
+            ]
+            .into_iter()
+            .map(signal)
+            .collect::<std::io::Result<_>>()?;
+            Ok(Self { signals })
+        }
+        #[cfg(not(unix))]
+        Ok(Self {})
+    }
+
+    /// Wait for the next shutdown signal.
+    pub async fn recv(&mut self) {
+        #[cfg(unix)]
+        futures::future::select_all(self.signals.iter_mut().map(|s| Box::pin(s.recv()))).await;
+        #[cfg(not(unix))]
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
 pub struct Tui {
     pub terminal: ratatui::Terminal<Backend<std::io::Stderr>>,
     pub task: JoinHandle<()>,
@@ -59,6 +95,8 @@ pub struct Tui {
     pub tick_rate: f64,
     pub mouse: bool,
     pub paste: bool,
+    pub focus: bool,
+    pub alternate_screen: bool,
 }
 
 impl Tui {
@@ -71,6 +109,8 @@ impl Tui {
         let task = tokio::spawn(async {});
         let mouse = false;
         let paste = false;
+        let focus = false;
+        let alternate_screen = true;
         Ok(Self {
             terminal,
             task,
@@ -81,6 +121,8 @@ impl Tui {
             tick_rate,
             mouse,
             paste,
+            focus,
+            alternate_screen,
         })
     }
 
@@ -100,12 +142,21 @@ impl Tui {
         self
     }
 
-    #[allow(dead_code)]
     pub fn paste(mut self, paste: bool) -> Self {
         self.paste = paste;
         self
     }
 
+    pub fn focus(mut self, focus: bool) -> Self {
+        self.focus = focus;
+        self
+    }
+
+    pub fn alternate_screen(mut self, alternate_screen: bool) -> Self {
+        self.alternate_screen = alternate_screen;
+        self
+    }
+
     pub fn start(&mut self) {
         let tick_delay = std::time::Duration::from_secs_f64(1.0 / self.tick_rate);
         let render_delay = std::time::Duration::from_secs_f64(1.0 / self.frame_rate);
@@ -115,6 +166,9 @@ impl Tui {
         let _event_tx = self.event_tx.clone();
         self.task = tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
+            let mut signals = ShutdownSignals::new()
+                .inspect_err(|e| log::warn!("Unable to listen for shutdown signals: {e}"))
+                .ok();
             let mut tick_interval = tokio::time::interval(tick_delay);
             let mut render_interval = tokio::time::interval(render_delay);
             _event_tx.send(Event::Init).unwrap();
@@ -126,6 +180,9 @@ impl Tui {
                     _ = _cancellation_token.cancelled() => {
                         break;
                     }
+                    _ = async { signals.as_mut().unwrap().recv().await }, if signals.is_some() => {
+                        _event_tx.send(Event::Quit).unwrap();
+                    }
                     maybe_event = crossterm_event => {
                         match maybe_event {
                             Some(Ok(evt)) => {
@@ -188,13 +245,24 @@ impl Tui {
 
     pub fn enter(&mut self) -> Result<()> {
         crossterm::terminal::enable_raw_mode()?;
-        crossterm::execute!(std::io::stdout(), EnterAlternateScreen, cursor::Hide)?;
+        if self.alternate_screen {
+            crossterm::execute!(std::io::stdout(), EnterAlternateScreen)?;
+        } else {
+            // frames cover the whole screen, so start from a blank one
+            crossterm::execute!(std::io::stdout(), Clear(ClearType::All))?;
+        }
+        crossterm::execute!(std::io::stdout(), cursor::Hide)?;
         if self.mouse {
             crossterm::execute!(std::io::stdout(), EnableMouseCapture)?;
         }
         if self.paste {
             crossterm::execute!(std::io::stdout(), EnableBracketedPaste)?;
         }
+        // terminals without focus reporting simply never send focus events
+        if self.focus && crossterm::execute!(std::io::stdout(), EnableFocusChange).is_err() {
+            log::warn!("Focus reporting is not supported by this terminal");
+            self.focus = false;
+        }
         self.start();
         Ok(())
     }
@@ -203,13 +271,26 @@ impl Tui {
         self.stop()?;
         if crossterm::terminal::is_raw_mode_enabled()? {
             self.flush()?;
+            if self.focus {
+                crossterm::execute!(std::io::stdout(), DisableFocusChange)?;
+            }
             if self.paste {
                 crossterm::execute!(std::io::stdout(), DisableBracketedPaste)?;
             }
             if self.mouse {
                 crossterm::execute!(std::io::stdout(), DisableMouseCapture)?;
             }
-            crossterm::execute!(std::io::stdout(), LeaveAlternateScreen, cursor::Show)?;
+            if self.alternate_screen {
+                crossterm::execute!(std::io::stdout(), LeaveAlternateScreen)?;
+            } else {
+                // room for whatever is printed after the TUI
+                crossterm::execute!(
+                    std::io::stdout(),
+                    Clear(ClearType::All),
+                    cursor::MoveTo(0, 0)
+                )?;
+            }
+            crossterm::execute!(std::io::stdout(), cursor::Show)?;
             crossterm::terminal::disable_raw_mode()?;
         }
         Ok(())
@@ -269,3 +350,17 @@ impl Drop for Tui {
         self.exit().unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn sigterm_is_a_shutdown_signal() {
+        let mut signals = ShutdownSignals::new().unwrap();
+        signal_hook::low_level::raise(signal_hook::consts::SIGTERM).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), signals.recv())
+            .await
+            .expect("SIGTERM was not received");
+    }
+}
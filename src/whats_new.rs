@@ -0,0 +1,59 @@
+/// Running version, stored as `last_version` in the config once seen.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Highlights of each release, newest first.
+const NOTES: &[(&str, &[&str])] = &[(
+    "0.1.0",
+    &[
+        "Tick-size price precision per symbol",
+        "Price chart (c), volume bars and update times (t)",
+        "Stablecoin spreads for the selected coin (g)",
+        "Pause (p), marks (Space) and copying the table (Y)",
+        "Bell on big moves of favorites (move_alert)",
+    ],
+)];
+
+/// `major.minor.patch` as numbers; missing or unparsable parts count as 0
+/// and any pre-release suffix is ignored.
+fn parse(version: &str) -> [u64; 3] {
+    let mut parts = version
+        .split(['-', '+'])
+        .next()
+        .unwrap_or_default()
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0));
+    [(); 3].map(|()| parts.next().unwrap_or(0))
+}
+
+/// Notes of the releases after `previous` up to the running one, newest
+/// first. Empty for a first run, where there is nothing to compare with,
+/// and when the version did not go up.
+pub fn since(previous: Option<&str>) -> Vec<(&'static str, &'static [&'static str])> {
+    let Some(previous) = previous.map(parse) else {
+        return Vec::new();
+    };
+    let current = parse(VERSION);
+    NOTES
+        .iter()
+        .filter(|(version, _)| {
+            let version = parse(version);
+            version > previous && version <= current
+        })
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert!(parse("0.10.0") > parse("0.9.3"));
+        assert_eq!(parse("1.2.0-beta.1"), [1, 2, 0]);
+        assert_eq!(parse("2"), [2, 0, 0]);
+        assert!(since(None).is_empty());
+        assert!(since(Some(VERSION)).is_empty());
+        assert_eq!(since(Some("0.0.1")).len(), 1);
+    }
+}
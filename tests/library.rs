@@ -0,0 +1,214 @@
+use coinmarket::{
+    compare::{compare, sort_comparisons},
+    filter::{self, filter_rows, match_ranges},
+    format::{
+        format_price, format_price_fixed, format_scientific, truncate_display, Notation,
+        NumberFormat,
+    },
+    sort::{sort_market_data, SortMode},
+    MarketRow,
+};
+
+fn row(symbol: &str, price: f64, change_pct: Option<f64>) -> MarketRow {
+    MarketRow {
+        symbol: symbol.to_owned(),
+        price,
+        change_pct,
+        change_7d: None,
+        vwap: None,
+        quote_volume: None,
+        high: None,
+        low: None,
+    }
+}
+
+fn rows() -> Vec<MarketRow> {
+    vec![
+        row("BTCUSDT", 65000.0, Some(-6.5)),
+        row("ETHUSDT", 3500.0, Some(2.0)),
+        row("DOGEUSDT", 0.15, Some(-8.0)),
+        row("NEWUSDT", 250.0, None),
+    ]
+}
+
+fn symbols(rows: &[MarketRow]) -> Vec<&str> {
+    rows.iter().map(|row| row.symbol.as_str()).collect()
+}
+
+#[test]
+fn filter_rows_without_expression_keeps_everything() {
+    assert_eq!(filter_rows(&rows(), None), rows());
+}
+
+#[test]
+fn filter_rows_applies_watch_expression() {
+    let expr = filter::parse("price > 100 and change% < -5").unwrap();
+    assert_eq!(symbols(&filter_rows(&rows(), Some(&expr))), ["BTCUSDT"]);
+}
+
+#[test]
+fn or_and_parentheses() {
+    let expr = filter::parse("(price < 1 or price >= 3500) and change% < 0").unwrap();
+    assert_eq!(
+        symbols(&filter_rows(&rows(), Some(&expr))),
+        ["BTCUSDT", "DOGEUSDT"]
+    );
+}
+
+#[test]
+fn missing_change_never_matches() {
+    let expr = filter::parse("change% != 0").unwrap();
+    assert!(!symbols(&filter_rows(&rows(), Some(&expr))).contains(&"NEWUSDT"));
+}
+
+#[test]
+fn parse_errors_are_reported() {
+    assert!(filter::parse("price >").is_err());
+    assert!(filter::parse("volume > 5").is_err());
+    assert!(filter::parse("price > 5 price").is_err());
+    assert!(filter::parse("(price > 5").is_err());
+}
+
+#[test]
+fn price_ranges_are_inclusive_or_open_ended() {
+    let band = |input| {
+        let expr = filter::parse_price_range(input).unwrap();
+        filter_rows(&rows(), Some(&expr))
+            .into_iter()
+            .map(|row| row.symbol)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(band("250-3500"), ["ETHUSDT", "NEWUSDT"]);
+    assert_eq!(band(" >1000 "), ["BTCUSDT", "ETHUSDT"]);
+    assert_eq!(band("<0.2"), ["DOGEUSDT"]);
+    assert_eq!(band("<=250"), ["DOGEUSDT", "NEWUSDT"]);
+    for invalid in ["500-100", "1e3-5", "abc", ">", "1-", "inf-5", "1.2.3-4"] {
+        assert!(filter::parse_price_range(invalid).is_err(), "{invalid}");
+    }
+}
+
+#[test]
+fn ties_sort_by_symbol_in_either_direction() {
+    let mut rows = vec![
+        row("SOLUSDT", 1.0, Some(2.0)),
+        row("ADAUSDT", 1.0, None),
+        row("BTCUSDT", 2.0, Some(2.0)),
+        row("ARBUSDT", 1.0, Some(2.0)),
+    ];
+    sort_market_data(&mut rows, SortMode::Price, true);
+    assert_eq!(symbols(&rows), ["BTCUSDT", "ADAUSDT", "ARBUSDT", "SOLUSDT"]);
+    sort_market_data(&mut rows, SortMode::Price, false);
+    assert_eq!(symbols(&rows), ["ADAUSDT", "ARBUSDT", "SOLUSDT", "BTCUSDT"]);
+    sort_market_data(&mut rows, SortMode::Change, true);
+    assert_eq!(symbols(&rows), ["ARBUSDT", "BTCUSDT", "SOLUSDT", "ADAUSDT"]);
+}
+
+#[test]
+fn volume_sort_puts_the_most_traded_first() {
+    let traded = |symbol, volume| MarketRow {
+        quote_volume: volume,
+        ..row(symbol, 1.0, None)
+    };
+    let mut rows = vec![
+        traded("DOGEUSDT", Some(5e8)),
+        traded("NEWUSDT", None),
+        traded("BTCUSDT", Some(2e9)),
+        traded("ETHUSDT", Some(1e9)),
+    ];
+    sort_market_data(&mut rows, SortMode::Volume, true);
+    assert_eq!(
+        symbols(&rows),
+        ["BTCUSDT", "ETHUSDT", "DOGEUSDT", "NEWUSDT"]
+    );
+}
+
+#[test]
+fn format_price_uses_locale_separators() {
+    let us = NumberFormat::from_locale("en-US").unwrap();
+    let de = NumberFormat::from_locale("de_DE.UTF-8").unwrap();
+    assert_eq!(format_price(1234567.891, &us), "1,234,567.891");
+    assert_eq!(format_price(1234567.891, &de), "1.234.567,891");
+    assert_eq!(format_price(0.00001234, &de), "0,00001234");
+    assert_eq!(format_price(-1000.0, &us), "-1,000");
+}
+
+#[test]
+fn unknown_locale_is_rejected() {
+    assert_eq!(NumberFormat::from_locale("xx-YY"), None);
+    assert_eq!(NumberFormat::from_locale("C"), Some(NumberFormat::US));
+}
+
+#[test]
+fn scientific_prices_have_the_same_width() {
+    let us = NumberFormat::US;
+    assert_eq!(format_scientific(0.00000001234, &us), "1.234e-08");
+    assert_eq!(format_scientific(65000.0, &us), "6.500e+04");
+    assert_eq!(format_scientific(-2.5, &us), "-2.500e+00");
+    assert_eq!(
+        format_scientific(1234.5, &NumberFormat::from_locale("de").unwrap()),
+        "1,234e+03"
+    );
+    assert_eq!(
+        Notation::Decimal.format(1234.5, &us),
+        format_price(1234.5, &us)
+    );
+}
+
+#[test]
+fn fixed_precision_pads_and_rounds() {
+    let us = NumberFormat::US;
+    assert_eq!(format_price_fixed(2.5, 2, &us), "2.50");
+    assert_eq!(format_price_fixed(1234.5678, 2, &us), "1,234.57");
+    assert_eq!(format_price_fixed(0.000012346, 8, &us), "0.00001235");
+    assert_eq!(format_price_fixed(-0.001, 2, &us), "0.00");
+    assert_eq!(format_price_fixed(1999.6, 0, &us), "2,000");
+}
+
+#[test]
+fn truncation_respects_graphemes_and_width() {
+    assert_eq!(truncate_display("BTCUSDT", 7), "BTCUSDT");
+    assert_eq!(truncate_display("BTCUSDT", 5), "BTCU…");
+    // wide glyphs take two columns and are never cut in half
+    assert_eq!(truncate_display("比特币USDT", 6), "比特…");
+    assert_eq!(truncate_display("比特币USDT", 5), "比特…");
+    // combining marks stay with their base letter
+    assert_eq!(truncate_display("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+    assert_eq!(truncate_display("BTC", 0), "");
+}
+
+#[test]
+fn search_matches_substrings_then_subsequences() {
+    let matched = |text: &'static str, query| {
+        match_ranges(text, query)
+            .into_iter()
+            .map(|range| &text[range])
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(matched("BTCUSDT", "btc"), ["BTC"]);
+    assert_eq!(matched("WBTCUSDT", "BTC"), ["BTC"]);
+    assert_eq!(match_ranges("WBTCUSDT", "BTC")[0].start, 1);
+    assert_eq!(matched("DOGEUSDT", "dgu"), ["D", "G", "U"]);
+    assert_eq!(matched("DOGEUSDT", "dge"), ["D", "GE"]);
+    assert!(matched("ETHUSDT", "btc").is_empty());
+    assert!(matched("ETHUSDT", "").is_empty());
+}
+
+#[test]
+fn compare_joins_snapshots_by_symbol() {
+    let a = [row("BTCUSDT", 100.0, None), row("OLDUSDT", 1.0, None)];
+    let b = [row("BTCUSDT", 110.0, None), row("NEWUSDT", 2.0, None)];
+    let mut rows = compare(&a, &b);
+    assert_eq!(
+        rows.iter().map(|x| x.symbol.as_str()).collect::<Vec<_>>(),
+        ["BTCUSDT", "NEWUSDT", "OLDUSDT"]
+    );
+    assert!((rows[0].delta_pct().unwrap() - 10.0).abs() < 1e-9);
+    assert_eq!((rows[1].price_a, rows[1].price_b), (None, Some(2.0)));
+    assert_eq!(rows[2].delta_pct(), None);
+
+    // missing deltas sort last in either direction
+    sort_comparisons(&mut rows, true, false);
+    assert_eq!(rows[0].symbol, "BTCUSDT");
+    sort_comparisons(&mut rows, true, true);
+    assert_eq!(rows[0].symbol, "BTCUSDT");
+}
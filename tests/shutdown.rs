@@ -0,0 +1,129 @@
+#![cfg(unix)]
+
+use std::{
+    fs::File,
+    io::{ErrorKind, Read},
+    os::fd::{AsRawFd, FromRawFd, OwnedFd},
+    process::{Command, Stdio},
+    ptr, thread,
+    time::{Duration, Instant},
+};
+
+const ENTER_ALTERNATE_SCREEN: &[u8] = b"\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &[u8] = b"\x1b[?1049l";
+
+/// A pseudo terminal as `(master, slave)`, 80x24 and in its default cooked
+/// mode.
+fn open_pty() -> (File, OwnedFd) {
+    let (mut master, mut slave) = (0, 0);
+    let size = libc::winsize {
+        ws_row: 24,
+        ws_col: 80,
+        ws_xpixel: 0,
+        ws_ypixel: 0,
+    };
+    // SAFETY: the out pointers are valid and the name buffer is not asked for
+    let opened =
+        unsafe { libc::openpty(&mut master, &mut slave, ptr::null_mut(), ptr::null(), &size) };
+    assert_eq!(opened, 0, "openpty failed");
+    // SAFETY: both descriptors were just opened and are owned here alone
+    let (master, slave) = unsafe { (File::from_raw_fd(master), OwnedFd::from_raw_fd(slave)) };
+    // SAFETY: setting a flag on a descriptor owned here
+    unsafe {
+        let flags = libc::fcntl(master.as_raw_fd(), libc::F_GETFL);
+        libc::fcntl(master.as_raw_fd(), libc::F_SETFL, flags | libc::O_NONBLOCK);
+    }
+    (master, slave)
+}
+
+/// Append what the program wrote to `output` until it contains `needle` or
+/// `until` passes.
+fn read_until(master: &mut File, output: &mut Vec<u8>, needle: &[u8], until: Instant) -> bool {
+    let mut buf = [0; 4096];
+    while Instant::now() < until {
+        if output.windows(needle.len()).any(|window| window == needle) {
+            return true;
+        }
+        match master.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => output.extend_from_slice(&buf[..n]),
+            Err(e) if e.kind() == ErrorKind::WouldBlock => thread::sleep(Duration::from_millis(10)),
+            // the slave side closed
+            Err(_) => break,
+        }
+    }
+    output.windows(needle.len()).any(|window| window == needle)
+}
+
+fn local_flags(fd: &OwnedFd) -> libc::tcflag_t {
+    // SAFETY: termios is plain data, filled in by tcgetattr
+    let mut termios = unsafe { std::mem::zeroed::<libc::termios>() };
+    // SAFETY: the descriptor is open and the pointer valid
+    assert_eq!(unsafe { libc::tcgetattr(fd.as_raw_fd(), &mut termios) }, 0);
+    termios.c_lflag
+}
+
+#[test]
+fn sigterm_restores_the_terminal() {
+    let (mut master, slave) = open_pty();
+    let cooked = local_flags(&slave);
+    assert_ne!(cooked & libc::ICANON, 0);
+
+    let dir = std::env::temp_dir().join(format!("coinmarket-shutdown-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let stdio = || Stdio::from(slave.try_clone().unwrap());
+    let mut child = Command::new(env!("CARGO_BIN_EXE_coinmarket"))
+        .env("COINMARKET_CONFIG", dir.join("config.json"))
+        .env("TERM", "xterm-256color")
+        .stdin(stdio())
+        .stdout(stdio())
+        .stderr(stdio())
+        .spawn()
+        .unwrap();
+
+    let mut output = Vec::new();
+    let deadline = Instant::now() + Duration::from_secs(20);
+    let entered = read_until(&mut master, &mut output, ENTER_ALTERNATE_SCREEN, deadline);
+    if !entered {
+        let _ = child.kill();
+    }
+    assert!(entered, "the TUI never started");
+    // raw mode is on by the time the screen is switched
+    assert_eq!(local_flags(&slave) & libc::ICANON, 0);
+    // the event task listening for signals starts just after
+    let settled = Instant::now() + Duration::from_secs(1);
+    read_until(&mut master, &mut output, LEAVE_ALTERNATE_SCREEN, settled);
+
+    // SAFETY: signalling a child that has not been reaped yet
+    let pid = libc::pid_t::try_from(child.id()).unwrap();
+    assert_eq!(unsafe { libc::kill(pid, libc::SIGTERM) }, 0);
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if Instant::now() > deadline {
+            let _ = child.kill();
+            panic!("still running after SIGTERM");
+        }
+        // keep draining so the program is never blocked writing
+        let soon = Instant::now() + Duration::from_millis(10);
+        read_until(&mut master, &mut output, LEAVE_ALTERNATE_SCREEN, soon);
+    };
+    let left = read_until(
+        &mut master,
+        &mut output,
+        LEAVE_ALTERNATE_SCREEN,
+        Instant::now() + Duration::from_secs(1),
+    );
+    let _ = std::fs::remove_dir_all(&dir);
+
+    assert!(status.success(), "exited with {status}");
+    assert!(left, "the alternate screen was not left");
+    let restored = local_flags(&slave);
+    assert_ne!(restored & libc::ICANON, 0, "raw mode is still on");
+    assert_eq!(
+        restored & libc::ECHO,
+        cooked & libc::ECHO,
+        "echo was not restored"
+    );
+}